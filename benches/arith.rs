@@ -0,0 +1,53 @@
+//! Compares `S21Decimal` arithmetic and formatting on native integers with
+//! the `BitStr`/`DecStr` views they used to be built on.
+//!
+//! Run with `cargo bench`.
+
+use s21_decimal::{BitStr, DecStr, S21Decimal};
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+fn bench<T>(name: &str, iters: u32, mut f: impl FnMut() -> T) -> Duration {
+    let start = Instant::now();
+
+    for _ in 0..iters {
+        black_box(f());
+    }
+
+    let per_iter = start.elapsed() / iters;
+    println!("{name:<40} {per_iter:>12.2?}/iter");
+
+    per_iter
+}
+
+fn main() {
+    let lhs: S21Decimal = "-8_700_600_500_400_300_200.100".parse().unwrap();
+    let rhs: S21Decimal = "123_456.789_012_345".parse().unwrap();
+
+    let native = bench("S21Decimal + S21Decimal", 1_000_000, || {
+        black_box(lhs) + black_box(rhs)
+    });
+
+    let mut lbs = BitStr::from(&lhs);
+    let rbs = BitStr::from(&rhs);
+    lbs.mul_pow10((rhs.scale() - lhs.scale()) as u32).unwrap();
+    lbs.scale = rhs.scale();
+
+    let bitstr = bench("BitStr + BitStr", 10_000, || lbs.clone() + rbs.clone());
+    println!(
+        "{:<40} {:>11.0}x",
+        "add speed-up",
+        bitstr.as_secs_f64() / native.as_secs_f64()
+    );
+
+    let native = bench("S21Decimal to_string", 100_000, || lhs.to_string());
+    let decstr = bench("DecStr::from(BitStr) to_string", 1_000, || {
+        DecStr::from(BitStr::from(&lhs)).to_string()
+    });
+    println!(
+        "{:<40} {:>11.0}x",
+        "format speed-up",
+        decstr.as_secs_f64() / native.as_secs_f64()
+    );
+}
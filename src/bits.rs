@@ -1,14 +1,51 @@
+#[cfg(feature = "alloc")]
+use alloc::{format, string::String};
+
+#[cfg(feature = "alloc")]
+use crate::S21Decimal;
+
+/// Binary rendering of a value's in-memory bits, most significant first.
+#[cfg(feature = "alloc")]
 pub trait Bits {
     fn bits(&self) -> String;
 }
 
-impl Bits for i32 {
+macro_rules! impl_bits {
+    ($($type:ty),*) => {
+        $(
+            #[cfg(feature = "alloc")]
+            impl Bits for $type {
+                fn bits(&self) -> String {
+                    format!("{:0width$b}", self, width = <$type>::BITS as usize)
+                }
+            }
+        )*
+    };
+}
+
+impl_bits!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// Labels the fields of the four words in the order of the C struct's
+/// `bits[3]` to `bits[0]`, for example:
+///
+/// ```text
+/// sign:1 reserved:0000000 scale:00000010 reserved:0000000000000000 | hi:00…0 | mid:00…0 | lo:00…0101
+/// ```
+#[cfg(feature = "alloc")]
+impl Bits for S21Decimal {
     fn bits(&self) -> String {
-        (0..32)
-            .into_iter()
-            .map(|i| char::from(get_bit(*self, i) as u8 + 48))
-            .rev()
-            .collect::<String>()
+        let flags = self.bits[3].bits();
+
+        format!(
+            "sign:{} reserved:{} scale:{} reserved:{} | hi:{} | mid:{} | lo:{}",
+            &flags[..1],
+            &flags[1..8],
+            &flags[8..16],
+            &flags[16..],
+            self.bits[2].bits(),
+            self.bits[1].bits(),
+            self.bits[0].bits(),
+        )
     }
 }
 
@@ -16,10 +53,52 @@ pub(crate) const fn get_bit(value: i32, bit: i32) -> i32 {
     (value >> bit) & 1
 }
 
-pub(crate) fn set_bit(value: &mut i32, bit: i32) {
+pub(crate) const fn set_bit(value: &mut i32, bit: i32) {
     *value |= 1 << bit;
 }
 
-pub(crate) fn unset_bit(value: &mut i32, bit: i32) {
+pub(crate) const fn unset_bit(value: &mut i32, bit: i32) {
     *value &= !(1 << bit);
 }
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bits_integers() {
+        assert_eq!("00000101", 5u8.bits());
+        assert_eq!("11111111", (-1i8).bits());
+        assert_eq!("1000000000000000", i16::MIN.bits());
+        assert_eq!(format!("{}101", "0".repeat(29)), 5i32.bits());
+        assert_eq!(64, u64::MAX.bits().len());
+        assert_eq!(format!("1{}", "0".repeat(127)), (1u128 << 127).bits());
+        assert_eq!(format!("{}1", "1".repeat(127)), (-1i128).bits());
+    }
+
+    #[test]
+    fn bits_decimal() {
+        let decimal = S21Decimal::new(-5, 2);
+        let zeros = "0".repeat(32);
+
+        assert_eq!(
+            format!(
+                "sign:1 reserved:0000000 scale:00000010 reserved:0000000000000000 | hi:{zeros} | mid:{zeros} | lo:{}101",
+                "0".repeat(29)
+            ),
+            decimal.bits()
+        );
+    }
+
+    #[test]
+    fn bits_decimal_reserved() {
+        let decimal = S21Decimal {
+            bits: [-1, 0, 1, 1 << 24 | 1],
+        };
+        let rendered = decimal.bits();
+
+        assert!(rendered
+            .starts_with("sign:0 reserved:0000001 scale:00000000 reserved:0000000000000001 | hi:"));
+        assert!(rendered.ends_with(&format!("| lo:{}", "1".repeat(32))));
+    }
+}
@@ -1,63 +1,128 @@
-use crate::DecStr;
-
-use super::{get_bit, Bits, S21Decimal, Sign};
+use alloc::format;
+use alloc::string::{String, ToString};
 
-const BITSTR_LEN: usize = 96;
+use crate::mantissa::POW10;
+use crate::parse::{parse_digits, parse_prefixed_digits, split_sign};
+use crate::DecStr;
 
-#[derive(PartialEq, Clone)]
-pub struct BitStr {
+use super::{get_bit, Bits, OverflowError, ParseDecimalError, S21Decimal, Sign};
+
+/// Bit-level view of a decimal with one ASCII `'0'`/`'1'` byte per bit, most
+/// significant first. Meant for debugging: `S21Decimal` does its arithmetic
+/// on native integers.
+///
+/// The width `N` defaults to the 96 bits of the `S21Decimal` mantissa. Wider
+/// instantiations hold intermediates of multiplication and division; use
+/// [`BitStr::resize`] or the `From`/`TryFrom` impls to convert between them.
+///
+/// Comparisons are numeric: `1.0 == 1` and `-0 == 0`.
+#[derive(Clone)]
+pub struct BitStr<const N: usize = 96> {
     pub sign: Sign,
     pub scale: i32,
-    pub bytes: [u8; BITSTR_LEN],
+    pub bytes: [u8; N],
 }
 
-impl BitStr {
-    pub const LENGTH: usize = BITSTR_LEN;
+impl<const N: usize> BitStr<N> {
+    pub const LENGTH: usize = N;
 
+    /// Builds a value from little-endian 32-bit words, like the mantissa words
+    /// of `S21Decimal::bits`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a set bit does not fit in `N` bits, see [`BitStr::try_new`].
     pub fn new(sign: Sign, scale: i32, bytes: &[i32]) -> Self {
-        let mut res = Self::from(bytes);
+        Self::try_new(sign, scale, bytes).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    pub fn try_new(sign: Sign, scale: i32, bytes: &[i32]) -> Result<Self, OverflowError> {
+        let mut res = Self::try_from(bytes)?;
         res.sign = sign;
         res.scale = scale;
 
-        res
+        Ok(res)
     }
 
-    pub fn from_str_radix(str: &str, radix: u32) -> Self {
-        let mut result = BitStr::default();
-        let point = str.find('.');
+    /// Parses an integer in any radix from 2 to 36. Radix 10 additionally
+    /// accepts a single decimal point, which sets the scale. A leading sign
+    /// and `_` separators are allowed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `radix` is not in the range from 2 to 36.
+    pub fn from_str_radix(str: &str, radix: u32) -> Result<Self, ParseDecimalError> {
+        let mut result = Self::default();
+        let (sign, scale) =
+            parse_digits(str, radix, |radix, digit| result.push_digit(radix, digit))?;
+
+        result.sign = sign;
+        result.scale = scale;
+
+        Ok(result)
+    }
 
-        match radix {
-            2 => BitStr::from(str),
-            10 => {
-                let iter = str.chars().peekable();
+    /// Parses an integer whose radix is given by a `0x`, `0o` or `0b` prefix,
+    /// falling back to radix 10 when there is none. The sign, if any, goes
+    /// before the prefix.
+    pub fn from_str_prefixed(str: &str) -> Result<Self, ParseDecimalError> {
+        let mut result = Self::default();
+        let (sign, scale) =
+            parse_prefixed_digits(str, |radix, digit| result.push_digit(radix, digit))?;
 
-                let mut size = iter
-                    .clone()
-                    .filter(|ch| ch.is_ascii_digit() || ch.is_ascii_hexdigit())
-                    .count();
+        result.sign = sign;
+        result.scale = scale;
 
-                iter.filter(|ch| ch.is_ascii_digit() || ch.is_ascii_hexdigit())
-                    .enumerate()
-                    .for_each(|(i, ch)| {
-                        result = result.clone() + BitStr::from(ch);
-                        if i < size - 1 {
-                            result.pow(1);
-                        }
-                    });
+        Ok(result)
+    }
 
-                if str.starts_with("-") {
-                    result.sign = Sign::Negative;
-                    size += 1;
-                }
+    /// Multiplies the magnitude by `radix` and adds `digit`, failing once a
+    /// set bit would be shifted out of the `N` bits.
+    fn push_digit(&mut self, radix: u32, digit: u32) -> Result<(), ParseDecimalError> {
+        let mut carry = digit;
 
-                if let Some(scale) = point {
-                    result.scale = (size - scale) as i32;
-                }
+        for byte in self.bytes.iter_mut().rev() {
+            let value = (*byte - b'0') as u32 * radix + carry;
+            *byte = b'0' + (value & 1) as u8;
+            carry = value >> 1;
+        }
 
-                result
-            }
-            _ => unimplemented!(),
+        match carry {
+            0 => Ok(()),
+            _ => Err(ParseDecimalError::Overflow),
+        }
+    }
+
+    /// Copies the value into a `BitStr` of width `M`, keeping sign and scale.
+    /// Fails if a set bit would be cut off when narrowing.
+    pub fn resize<const M: usize>(&self) -> Result<BitStr<M>, OverflowError> {
+        let significant = self.msbi().map_or(0, |msbi| N - msbi);
+
+        if significant > M {
+            return Err(OverflowError);
         }
+
+        let mut result = BitStr::<M> {
+            sign: self.sign.clone(),
+            scale: self.scale,
+            bytes: [48; M],
+        };
+
+        let width = N.min(M);
+        result.bytes[M - width..].copy_from_slice(&self.bytes[N - width..]);
+
+        Ok(result)
+    }
+
+    /// Right-aligns already valid `'0'`/`'1'` bytes; longer input yields zero.
+    fn from_ascii(bytes: &[u8]) -> Self {
+        let mut bitstr = Self::default();
+
+        if bytes.len() <= N {
+            bitstr.bytes[N - bytes.len()..].copy_from_slice(bytes);
+        }
+
+        bitstr
     }
 
     pub fn all_zeroes(&self) -> bool {
@@ -69,53 +134,156 @@ impl BitStr {
     }
 
     pub fn shift(&mut self, offset: u32) {
-        (0..offset).for_each(|_| {
-            for i in 0..Self::LENGTH - 1 {
-                self.bytes[i] = self.bytes[i + 1];
-            }
-            self.bytes[Self::LENGTH - 1] = '0' as u8;
-        });
+        *self <<= offset;
     }
 
     pub fn invert(&mut self, upto: usize) {
         for k in Self::LENGTH - upto..Self::LENGTH {
             if self.bytes[k] - 48 == 1 {
-                self.bytes[k] = '0' as u8;
+                self.bytes[k] = b'0';
             } else {
-                self.bytes[k] = '1' as u8;
+                self.bytes[k] = b'1';
             }
         }
     }
 
-    pub fn pow(&mut self, exp: u32) {
-        let mut exponent = 10;
-        let mut result = BitStr::default();
-        (0..exp - 1).for_each(|_| exponent *= 10);
+    /// Multiplies by `10^exp` in place, keeping sign and scale. On overflow
+    /// `self` is left unchanged. Exponents above 28 only succeed for zero.
+    pub fn mul_pow10(&mut self, exp: u32) -> Result<(), OverflowError> {
+        if self.all_zeroes() {
+            return Ok(());
+        }
 
-        for i in 0..32u32 {
-            if get_bit(exponent, i as i32) == 1 {
-                let mut mask = self.clone();
-                mask.shift(i);
-                result = result + mask;
-            }
+        let power = *POW10.get(exp as usize).ok_or(OverflowError)?;
+
+        if (u128::BITS - power.leading_zeros()) as usize > N {
+            return Err(OverflowError);
         }
 
-        self.bytes = result.bytes;
+        let factor = Self::from_ascii(format!("{power:b}").as_bytes());
+        self.bytes = self.mul_magnitude(&factor).ok_or(OverflowError)?.bytes;
+
+        Ok(())
+    }
+
+    /// Returns bit `index`, counting from the least significant.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not below `N`.
+    pub fn get_bit(&self, index: usize) -> bool {
+        self.bytes[Self::byte_position(index)] == b'1'
+    }
+
+    /// Sets bit `index`, counting from the least significant.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not below `N`.
+    pub fn set_bit(&mut self, index: usize) {
+        self.bytes[Self::byte_position(index)] = b'1';
     }
 
+    /// Clears bit `index`, counting from the least significant.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not below `N`.
+    pub fn clear_bit(&mut self, index: usize) {
+        self.bytes[Self::byte_position(index)] = b'0';
+    }
+
+    /// Iterates over the bits, least significant first. Use `.rev()` to go
+    /// from the most significant.
+    pub fn iter_bits(&self) -> impl DoubleEndedIterator<Item = bool> + ExactSizeIterator + '_ {
+        self.bytes.iter().rev().map(|&byte| byte == b'1')
+    }
+
+    fn byte_position(index: usize) -> usize {
+        assert!(index < N, "bit index {index} is out of range of 0..{N}");
+        N - 1 - index
+    }
+
+    /// Index of the most significant set bit, counted from the left, or
+    /// `None` if no bit is set.
     pub fn msbi(&self) -> Option<usize> {
-        self.bytes.iter().position(|&b| b == 49)
+        let zeros = self.leading_zeros() as usize;
+        (zeros < N).then_some(zeros)
+    }
+
+    pub fn leading_zeros(&self) -> u32 {
+        self.bytes.iter().take_while(|&&b| b == b'0').count() as u32
     }
 
-    pub fn cmp_bytes(&self, other: &Self) -> std::cmp::Ordering {
+    pub fn trailing_zeros(&self) -> u32 {
+        self.bytes.iter().rev().take_while(|&&b| b == b'0').count() as u32
+    }
+
+    pub fn count_ones(&self) -> u32 {
+        self.bytes.iter().filter(|&&b| b == b'1').count() as u32
+    }
+
+    /// Compares the raw bits, ignoring sign and scale.
+    pub fn cmp_bytes(&self, other: &Self) -> core::cmp::Ordering {
         self.bytes.cmp(&other.bytes)
     }
 
-    pub fn add_upto(&self, other: &BitStr, upto: usize) -> Self {
-        let mut result = BitStr {
+    /// Compares absolute values after aligning the scales.
+    pub fn cmp_magnitude(&self, other: &Self) -> core::cmp::Ordering {
+        if self.scale < other.scale {
+            Self::cmp_rescaled(self, other)
+        } else {
+            Self::cmp_rescaled(other, self).reverse()
+        }
+    }
+
+    /// Compares signed values, so that `-0` equals `0` and `1.0` equals `1`.
+    pub fn cmp_value(&self, other: &Self) -> core::cmp::Ordering {
+        use core::cmp::Ordering::*;
+
+        match (self.is_negative(), other.is_negative()) {
+            (false, false) => self.cmp_magnitude(other),
+            (true, true) => other.cmp_magnitude(self),
+            (false, true) => Greater,
+            (true, false) => Less,
+        }
+    }
+
+    fn is_negative(&self) -> bool {
+        self.sign == Sign::Negative && !self.all_zeroes()
+    }
+
+    /// Compares the magnitude of `lower`, brought up to the scale of `higher`,
+    /// with that of `higher`. A rescaled value that overflows `N` bits is
+    /// necessarily the larger one.
+    fn cmp_rescaled(lower: &Self, higher: &Self) -> core::cmp::Ordering {
+        use core::cmp::Ordering::*;
+
+        if lower.all_zeroes() {
+            return if higher.all_zeroes() { Equal } else { Less };
+        }
+
+        let mut lower = lower.clone();
+        let mut diff = higher.scale.abs_diff(lower.scale);
+
+        while diff > 0 {
+            let exp = diff.min(28);
+
+            if lower.mul_pow10(exp).is_err() {
+                return Greater;
+            }
+
+            diff -= exp;
+        }
+
+        lower.cmp_bytes(higher)
+    }
+
+    pub fn add_upto(&self, other: &Self, upto: usize) -> Self {
+        let mut result = Self {
             sign: self.sign.clone(),
             scale: self.scale,
-            bytes: [48; 96],
+            bytes: [48; N],
         };
         let mut carry = 0;
 
@@ -129,7 +297,7 @@ impl BitStr {
                 let rhs_bit = other.bytes[i] - 48;
 
                 if bit ^ rhs_bit ^ carry == 1 {
-                    result.bytes[i] = '1' as u8;
+                    result.bytes[i] = b'1';
                     if bit + rhs_bit + carry < 3 {
                         carry = 0;
                     }
@@ -141,43 +309,153 @@ impl BitStr {
         result
     }
 
-    fn add_negative(&self, rhs: &BitStr) -> Self {
-        let mut result = BitStr::default();
-        let mut left = self.clone();
-        let mut right = rhs.clone();
+    fn add_signed(&self, rhs: &Self) -> Self {
+        use Sign::*;
 
-        match self.cmp_bytes(&right) {
-            std::cmp::Ordering::Greater => {
-                if let Some(msbi) = self.msbi() {
-                    right.invert(BitStr::LENGTH - msbi);
-                }
+        let (left, right) = Self::align(self, rhs);
 
-                if self.sign == Sign::Negative {
-                    result.sign = Sign::Negative;
-                }
-            }
+        match (&left.sign, &right.sign) {
+            (Positive, Positive) | (Negative, Negative) => left.add_positive(&right),
+            (_, _) => left.add_negative(&right),
+        }
+    }
 
-            _ => {
-                if let Some(msbi) = right.msbi() {
-                    left.invert(BitStr::LENGTH - msbi);
+    /// Scales up the operand with the lower scale to the scale of the other
+    /// one, panicking like `Add` if it does not fit in `N` bits.
+    fn align(left: &Self, right: &Self) -> (Self, Self) {
+        let swapped = left.scale > right.scale;
+        let (low, high) = if swapped {
+            (right, left)
+        } else {
+            (left, right)
+        };
+        let mut low = low.clone();
+        let mut diff = high.scale.abs_diff(low.scale);
+
+        while diff > 0 && !low.all_zeroes() {
+            let exp = diff.min(28);
+
+            if low.mul_pow10(exp).is_err() {
+                match low.sign {
+                    Sign::Positive => panic!("overflow"),
+                    Sign::Negative => panic!("underflow"),
                 }
             }
+
+            diff -= exp;
         }
 
-        left.sign = Sign::Positive;
-        let lbs_add_one = left.add_upto(&BitStr::from("1"), BitStr::LENGTH);
-        let sum = right.add_upto(&lbs_add_one, BitStr::LENGTH - right.msbi().unwrap());
+        low.scale = high.scale;
 
-        if !sum.all_zeroes() {
+        if swapped {
+            (high.clone(), low)
+        } else {
+            (low, high.clone())
+        }
+    }
+
+    fn add_negative(&self, rhs: &Self) -> Self {
+        let (larger, smaller) = match self.cmp_bytes(rhs) {
+            core::cmp::Ordering::Less => (rhs, self),
+            _ => (self, rhs),
+        };
+        let mut result = larger.sub_magnitude(smaller);
+
+        if result.all_zeroes() {
+            result.sign = Sign::Positive;
+            result.scale = 0;
+        } else {
+            result.sign = larger.sign.clone();
             result.scale = self.scale;
         }
 
-        result.bytes = sum.bytes;
         result
     }
 
+    /// Subtracts the magnitude of `rhs` in two's complement, wrapping at `N`
+    /// bits. Keeps the sign and scale of `self`.
+    fn sub_magnitude(&self, rhs: &Self) -> Self {
+        let negated = (!rhs.clone()).add_upto(&Self::from_ascii(b"1"), N);
+        self.add_upto(&negated, N)
+    }
+
+    /// Divides with truncation towards zero, returning the quotient and the
+    /// remainder. The remainder takes the sign of `self`, as for primitive
+    /// integers.
+    ///
+    /// Scales are treated like those of decimals: the quotient has scale
+    /// `self.scale - rhs.scale` and the remainder keeps `self.scale`, so that
+    /// `self == quotient * rhs + remainder` holds for the values.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rhs` is zero.
+    pub fn div_rem(&self, rhs: &Self) -> (Self, Self) {
+        assert!(!rhs.all_zeroes(), "division by zero");
+
+        let mut quotient = Self::default();
+        let mut remainder = Self::default();
+
+        for i in 0..N {
+            let carry = remainder.bytes[0] == b'1';
+            remainder <<= 1;
+            remainder.bytes[N - 1] = self.bytes[i];
+
+            if carry || remainder.cmp_bytes(rhs).is_ge() {
+                remainder = remainder.sub_magnitude(rhs);
+                quotient.bytes[i] = b'1';
+            }
+        }
+
+        let quotient_sign = if self.sign == rhs.sign {
+            Sign::Positive
+        } else {
+            Sign::Negative
+        };
+
+        (
+            quotient.signed(quotient_sign, self.scale - rhs.scale),
+            remainder.signed(self.sign.clone(), self.scale),
+        )
+    }
+
+    /// Multiplies the magnitudes by shift-and-add, or returns `None` if the
+    /// product does not fit in `N` bits.
+    fn mul_magnitude(&self, rhs: &Self) -> Option<Self> {
+        let mut result = Self::default();
+
+        for i in 0..N as u32 {
+            if rhs.bytes[N - 1 - i as usize] == b'0' {
+                continue;
+            }
+
+            if self.leading_zeros() < i {
+                return None;
+            }
+
+            let addend = self.clone() << i;
+            result = result.add_upto(&addend, N);
+
+            if result.cmp_bytes(&addend).is_lt() {
+                return None;
+            }
+        }
+
+        Some(result)
+    }
+
+    /// Sets sign and scale, keeping zero positive with scale 0 like `Add`.
+    fn signed(mut self, sign: Sign, scale: i32) -> Self {
+        if !self.all_zeroes() {
+            self.sign = sign;
+            self.scale = scale;
+        }
+
+        self
+    }
+
     fn add_positive(&self, rhs: &Self) -> Self {
-        let mut result = BitStr::default();
+        let mut result = Self::default();
         let mut carry = 0;
 
         self.bytes.iter().enumerate().rev().for_each(|(i, bit)| {
@@ -185,7 +463,7 @@ impl BitStr {
             let rhs_bit = rhs.bytes[i] - 48;
 
             if bit ^ rhs_bit ^ carry == 1 {
-                result.bytes[i] = '1' as u8;
+                result.bytes[i] = b'1';
                 if bit + rhs_bit + carry < 3 {
                     carry = 0;
                 }
@@ -210,34 +488,170 @@ impl BitStr {
     }
 }
 
-impl Default for BitStr {
+impl<const N: usize> Default for BitStr<N> {
     fn default() -> Self {
         Self {
             sign: Sign::Positive,
             scale: 0,
-            bytes: [48; 96],
+            bytes: [48; N],
         }
     }
 }
 
-impl std::ops::Add for BitStr {
+impl<const N: usize> core::ops::Add for BitStr<N> {
     type Output = Self;
 
     fn add(self, rhs: Self) -> Self::Output {
-        use Sign::*;
+        self.add_signed(&rhs)
+    }
+}
 
-        match (&self.sign, &rhs.sign) {
-            (Positive, Positive) | (Negative, Negative) => self.add_positive(&rhs),
-            (_, _) => self.add_negative(&rhs),
+impl<const N: usize> PartialEq for BitStr<N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp_value(other).is_eq()
+    }
+}
+
+impl<const N: usize> Eq for BitStr<N> {}
+
+impl<const N: usize> Ord for BitStr<N> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.cmp_value(other)
+    }
+}
+
+impl<const N: usize> PartialOrd for BitStr<N> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const N: usize> core::ops::Sub for BitStr<N> {
+    type Output = Self;
+
+    fn sub(self, mut rhs: Self) -> Self::Output {
+        rhs.sign = match rhs.sign {
+            Sign::Positive => Sign::Negative,
+            Sign::Negative => Sign::Positive,
+        };
+
+        self.add_signed(&rhs)
+    }
+}
+
+/// Shift-and-add multiplication. The scale of the product is the sum of the
+/// scales, and it panics like `Add` if the product does not fit in `N` bits.
+impl<const N: usize> core::ops::Mul for BitStr<N> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        let sign = if self.sign == rhs.sign {
+            Sign::Positive
+        } else {
+            Sign::Negative
+        };
+
+        match self.mul_magnitude(&rhs) {
+            Some(result) => result.signed(sign, self.scale + rhs.scale),
+            None => match sign {
+                Sign::Positive => panic!("overflow"),
+                Sign::Negative => panic!("underflow"),
+            },
         }
     }
 }
 
+/// Indexes the bits, least significant first.
+impl<const N: usize> core::ops::Index<usize> for BitStr<N> {
+    type Output = bool;
+
+    fn index(&self, index: usize) -> &bool {
+        if self.get_bit(index) {
+            &true
+        } else {
+            &false
+        }
+    }
+}
+
+/// Shifting by `N` or more bits clears every bit instead of panicking.
+impl<const N: usize> core::ops::Shl<u32> for BitStr<N> {
+    type Output = Self;
+
+    fn shl(mut self, rhs: u32) -> Self::Output {
+        self <<= rhs;
+        self
+    }
+}
+
+impl<const N: usize> core::ops::ShlAssign<u32> for BitStr<N> {
+    fn shl_assign(&mut self, rhs: u32) {
+        let kept = N.saturating_sub(rhs as usize);
+        self.bytes.rotate_left(N.min(rhs as usize));
+        self.bytes[kept..].fill(b'0');
+    }
+}
+
+/// Shifting by `N` or more bits clears every bit instead of panicking.
+impl<const N: usize> core::ops::Shr<u32> for BitStr<N> {
+    type Output = Self;
+
+    fn shr(mut self, rhs: u32) -> Self::Output {
+        self >>= rhs;
+        self
+    }
+}
+
+impl<const N: usize> core::ops::ShrAssign<u32> for BitStr<N> {
+    fn shr_assign(&mut self, rhs: u32) {
+        let offset = N.min(rhs as usize);
+        self.bytes.rotate_right(offset);
+        self.bytes[..offset].fill(b'0');
+    }
+}
+
+/// Flips every bit of the magnitude; sign and scale are kept.
+impl<const N: usize> core::ops::Not for BitStr<N> {
+    type Output = Self;
+
+    fn not(mut self) -> Self::Output {
+        self.bytes.iter_mut().for_each(|b| *b ^= 1);
+        self
+    }
+}
+
+/// Bitwise operators work on the magnitude and keep the sign and scale of the
+/// left operand.
+macro_rules! bitstr_bitop {
+    ($trait:ident, $method:ident, $assign_trait:ident, $assign_method:ident, $op:tt) => {
+        impl<const N: usize> core::ops::$trait for BitStr<N> {
+            type Output = Self;
+
+            fn $method(mut self, rhs: Self) -> Self::Output {
+                core::ops::$assign_trait::$assign_method(&mut self, rhs);
+                self
+            }
+        }
+
+        impl<const N: usize> core::ops::$assign_trait for BitStr<N> {
+            fn $assign_method(&mut self, rhs: Self) {
+                for (lhs, rhs) in self.bytes.iter_mut().zip(rhs.bytes) {
+                    *lhs = b'0' + ((*lhs - b'0') $op (rhs - b'0'));
+                }
+            }
+        }
+    };
+}
+
+bitstr_bitop!(BitAnd, bitand, BitAndAssign, bitand_assign, &);
+bitstr_bitop!(BitOr, bitor, BitOrAssign, bitor_assign, |);
+bitstr_bitop!(BitXor, bitxor, BitXorAssign, bitxor_assign, ^);
+
 macro_rules! bitstr_from_decimal {
     ($type:ty) => {
         impl From<$type> for BitStr {
             fn from(decimal: $type) -> Self {
-                let mut bstr: BitStr = decimal.bits[0..3]
+                let bstr = decimal.bits[0..3]
                     .iter()
                     .rev()
                     .map(|i| i.bits())
@@ -245,8 +659,8 @@ macro_rules! bitstr_from_decimal {
                         acc.push_str(&i);
                         acc
                     })
-                    .unwrap()
-                    .into();
+                    .unwrap();
+                let mut bstr = BitStr::from_ascii(bstr.as_bytes());
 
                 bstr.sign = decimal.sign();
                 bstr.scale = decimal.scale();
@@ -261,87 +675,102 @@ bitstr_from_decimal!(S21Decimal);
 bitstr_from_decimal!(&S21Decimal);
 bitstr_from_decimal!(&mut S21Decimal);
 
-impl From<&[i32]> for BitStr {
-    fn from(s: &[i32]) -> Self {
-        let s = s
-            .iter()
-            .fold(Vec::new(), |mut acc, bit| {
-                let chars = (0..32).fold(Vec::new(), |mut v, i| {
-                    v.push((get_bit(*bit, i) as u8 + 48) as char);
-                    v
-                });
+/// Reads little-endian 32-bit words and fails if a set bit lands at index
+/// `N` or above.
+impl<const N: usize> TryFrom<&[i32]> for BitStr<N> {
+    type Error = OverflowError;
 
-                let string = chars.iter().rev().collect::<String>();
-                acc.push(string);
+    fn try_from(words: &[i32]) -> Result<Self, Self::Error> {
+        let mut result = Self::default();
 
-                acc
-            })
-            .iter()
-            .rev()
-            .fold(String::new(), |mut acc, s| {
-                acc.push_str(s);
+        for (position, &word) in words.iter().enumerate() {
+            for bit in (0..32).filter(|&bit| get_bit(word, bit) == 1) {
+                let index = position * 32 + bit as usize;
 
-                acc
-            });
+                if index >= N {
+                    return Err(OverflowError);
+                }
 
-        BitStr::from(s)
+                result.set_bit(index);
+            }
+        }
+
+        Ok(result)
     }
 }
 
-impl From<String> for BitStr {
-    fn from(s: String) -> Self {
-        let mut bitstr = BitStr::default();
-
-        if s.len() == Self::LENGTH {
-            s.as_bytes()
-                .iter()
-                .enumerate()
-                .for_each(|(i, byte)| bitstr.bytes[i] = *byte);
-        } else if s.len() < Self::LENGTH {
-            let diff = Self::LENGTH - s.len();
-            s.as_bytes()
-                .iter()
-                .enumerate()
-                .for_each(|(i, byte)| bitstr.bytes[i + diff] = *byte);
-        }
+/// Deprecated: copies the bytes without validating them and yields zero for
+/// input longer than `N`. Use [`str::parse::<BitStr>`](str::parse) instead.
+/// Rust can't attach `#[deprecated]` to trait impls, so this is not reported
+/// by the compiler.
+impl<const N: usize> From<&str> for BitStr<N> {
+    fn from(s: &str) -> Self {
+        Self::from_ascii(s.as_bytes())
+    }
+}
 
-        bitstr
+/// Deprecated, see the [`From<&str>`](#impl-From%3C%26str%3E-for-BitStr%3CN%3E) impl.
+impl<const N: usize> From<String> for BitStr<N> {
+    fn from(s: String) -> Self {
+        Self::from(s.as_str())
     }
 }
 
-impl From<&str> for BitStr {
-    fn from(s: &str) -> Self {
-        let mut bitstr = BitStr::default();
-
-        if s.len() == Self::LENGTH {
-            s.as_bytes()
-                .iter()
-                .enumerate()
-                .for_each(|(i, byte)| bitstr.bytes[i] = *byte);
-        } else if s.len() < Self::LENGTH {
-            let diff = Self::LENGTH - s.len();
-            s.as_bytes()
-                .iter()
-                .enumerate()
-                .for_each(|(i, byte)| bitstr.bytes[i + diff] = *byte);
+/// Parses an optionally signed binary integer with an optional `0b` prefix
+/// and `_` separators. Leading zeros don't count towards the width `N`.
+impl<const N: usize> core::str::FromStr for BitStr<N> {
+    type Err = ParseDecimalError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (sign, digits) = split_sign(s)?;
+        let digits = match digits.get(..2) {
+            Some("0b" | "0B") => &digits[2..],
+            _ => digits,
+        };
+        let mut result = Self {
+            sign,
+            ..Self::default()
+        };
+        let mut width = 0;
+        let mut overflow = false;
+
+        for byte in digits.bytes().rev() {
+            match byte {
+                b'_' => continue,
+                b'0' | b'1' if width < N => result.bytes[N - 1 - width] = byte,
+                b'0' => (),
+                b'1' => overflow = true,
+                _ => return Err(ParseDecimalError::InvalidDigit),
+            }
+
+            width += 1;
         }
 
-        bitstr
+        match (width, overflow) {
+            (0, _) => Err(ParseDecimalError::InvalidDigit),
+            (_, true) => Err(ParseDecimalError::Overflow),
+            _ => Ok(result),
+        }
     }
 }
 
-impl From<char> for BitStr {
+/// Converts a hex digit.
+///
+/// # Panics
+///
+/// Panics if `ch` is not a hex digit or its value does not fit in `N` bits.
+impl<const N: usize> From<char> for BitStr<N> {
     fn from(ch: char) -> Self {
-        match ch.to_ascii_lowercase() {
-            '0'..='9' => BitStr::from(format!("{:b}", ch as u8 - 48)),
-            'a'..='f' => BitStr::from(format!("{:b}", ch as u8 - 87)),
-            _ => panic!("char is not in range of '0'..='9' || 'a'..='f'"),
-        }
+        let digit = ch
+            .to_digit(16)
+            .unwrap_or_else(|| panic!("char is not in range of '0'..='9' || 'a'..='f'"));
+
+        Self::try_from(&[digit as i32][..]).unwrap_or_else(|err| panic!("{err}"))
     }
 }
 
-impl From<BitStr> for String {
-    fn from(b: BitStr) -> Self {
+impl<const N: usize> From<BitStr<N>> for String {
+    fn from(b: BitStr<N>) -> Self {
         b.bytes.iter().fold(String::new(), |mut acc, byte| {
             acc.push(*byte as char);
             acc
@@ -349,8 +778,8 @@ impl From<BitStr> for String {
     }
 }
 
-impl From<&BitStr> for String {
-    fn from(b: &BitStr) -> Self {
+impl<const N: usize> From<&BitStr<N>> for String {
+    fn from(b: &BitStr<N>) -> Self {
         b.bytes.iter().fold(String::new(), |mut acc, byte| {
             acc.push(*byte as char);
             acc
@@ -358,18 +787,59 @@ impl From<&BitStr> for String {
     }
 }
 
-impl From<DecStr> for BitStr {
-    fn from(ds: DecStr) -> Self {
-        if ds.len() > 28 && ds.scale > 0 {
-            BitStr::from_str_radix(&ds.banker_round().to_string(), 10)
-        } else {
-            BitStr::from_str_radix(&ds.to_string(), 10)
+/// Rounds half to even to the largest scale of at most 28 at which the value
+/// fits in 96 bits. Every candidate is rounded from the original digits, so
+/// the value is rounded only once. Fails if the integral part alone does not
+/// fit.
+impl TryFrom<DecStr> for BitStr {
+    type Error = OverflowError;
+
+    fn try_from(ds: DecStr) -> Result<Self, Self::Error> {
+        let ds = match ds.scale {
+            scale if scale < 0 => ds.rescale(-scale),
+            _ => ds,
+        };
+        let mut scale = ds.scale.min(28);
+
+        loop {
+            match BitStr::from_str_radix(&ds.round_to(scale).to_string(), 10) {
+                Err(ParseDecimalError::Overflow) if scale > 0 => scale -= 1,
+                result => return result.map_err(|_| OverflowError),
+            }
         }
     }
 }
 
-impl std::fmt::Debug for BitStr {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+macro_rules! bitstr_resize {
+    ($($narrow:literal => $wide:literal),*) => {
+        $(
+            impl From<BitStr<$narrow>> for BitStr<$wide> {
+                fn from(bstr: BitStr<$narrow>) -> Self {
+                    bstr.resize().unwrap()
+                }
+            }
+
+            impl TryFrom<BitStr<$wide>> for BitStr<$narrow> {
+                type Error = OverflowError;
+
+                fn try_from(bstr: BitStr<$wide>) -> Result<Self, Self::Error> {
+                    bstr.resize()
+                }
+            }
+        )*
+    };
+}
+
+bitstr_resize!(
+    32 => 64, 32 => 96, 32 => 128, 32 => 192, 32 => 256,
+    64 => 96, 64 => 128, 64 => 192, 64 => 256,
+    96 => 128, 96 => 192, 96 => 256,
+    128 => 192, 128 => 256,
+    192 => 256
+);
+
+impl<const N: usize> core::fmt::Debug for BitStr<N> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.debug_struct("BitStr")
             .field("sign", &self.sign)
             .field("scale", &self.scale)
@@ -382,6 +852,8 @@ impl std::fmt::Debug for BitStr {
 mod tests {
     use super::*;
 
+    type BitStr = super::BitStr;
+
     #[test]
     fn bitstr_from_char() {
         let test_cases = ['0', '1', '2', '5', '9', 'a', 'f'];
@@ -402,14 +874,14 @@ mod tests {
 
     #[test]
     fn bitstr_from_str_radix_10_num_45() {
-        let bstr = BitStr::from_str_radix("45", 10);
+        let bstr = BitStr::from_str_radix("45", 10).unwrap();
 
         assert_eq!(BitStr::from("101101"), bstr);
     }
 
     #[test]
     fn bitstr_from_str_radix_10_num_45_scale_1() {
-        let bstr = BitStr::from_str_radix("-4.5", 10);
+        let bstr = BitStr::from_str_radix("-4.5", 10).unwrap();
         let mut expecting = BitStr::from("101101");
         expecting.sign = Sign::Negative;
         expecting.scale = 1;
@@ -419,7 +891,7 @@ mod tests {
 
     #[test]
     fn bitstr_from_str_radix_10_num_5_scale_5() {
-        let bstr = BitStr::from_str_radix("0.00005", 10);
+        let bstr = BitStr::from_str_radix("0.00005", 10).unwrap();
         let mut expecting = BitStr::from("101");
         expecting.scale = 5;
 
@@ -428,40 +900,137 @@ mod tests {
 
     #[test]
     fn bitstr_from_str_radix_10_num_max() {
-        let bstr = BitStr::from_str_radix("79_228_162_514_264_337_593_543_950_335", 10);
+        let bstr = BitStr::from_str_radix("79_228_162_514_264_337_593_543_950_335", 10).unwrap();
+
+        assert_eq!(BitStr::from("1".repeat(BitStr::LENGTH)), bstr);
+    }
 
+    #[test]
+    fn bitstr_from_str_radix_10_rejects_hex_digits() {
         assert_eq!(
-            BitStr::from(format!("{}", "1".repeat(BitStr::LENGTH))),
-            bstr
+            Err(ParseDecimalError::InvalidDigit),
+            BitStr::from_str_radix("4f", 10)
+        );
+    }
+
+    #[test]
+    fn bitstr_from_str_radix_10_overflow() {
+        assert_eq!(
+            Err(ParseDecimalError::Overflow),
+            BitStr::from_str_radix("79_228_162_514_264_337_593_543_950_336", 10)
+        );
+    }
+
+    #[test]
+    fn bitstr_from_str_radix_2() {
+        let bstr = BitStr::from_str_radix("-1011_0001", 2).unwrap();
+        let mut expecting = BitStr::from("10110001");
+        expecting.sign = Sign::Negative;
+
+        assert_eq!(expecting, bstr);
+    }
+
+    #[test]
+    fn bitstr_from_str_radix_2_rejects_digit_2() {
+        assert_eq!(
+            Err(ParseDecimalError::InvalidDigit),
+            BitStr::from_str_radix("102", 2)
+        );
+    }
+
+    #[test]
+    fn bitstr_from_str_radix_8() {
+        assert_eq!(
+            BitStr::from("111101"),
+            BitStr::from_str_radix("75", 8).unwrap()
+        );
+    }
+
+    #[test]
+    fn bitstr_from_str_radix_16_mixed_case() {
+        assert_eq!(
+            BitStr::from("1010101111001101"),
+            BitStr::from_str_radix("aBcD", 16).unwrap()
+        );
+    }
+
+    #[test]
+    fn bitstr_from_str_radix_16_max() {
+        assert_eq!(
+            BitStr::from("1".repeat(BitStr::LENGTH)),
+            BitStr::from_str_radix("FFFF_FFFF_FFFF_FFFF_FFFF_FFFF", 16).unwrap()
+        );
+    }
+
+    #[test]
+    fn bitstr_from_str_radix_36() {
+        assert_eq!(
+            BitStr::from("100011"),
+            BitStr::from_str_radix("Z", 36).unwrap()
+        );
+    }
+
+    #[test]
+    fn bitstr_from_str_radix_16_rejects_point() {
+        assert_eq!(
+            Err(ParseDecimalError::InvalidDigit),
+            BitStr::from_str_radix("1.5", 16)
+        );
+    }
+
+    #[test]
+    fn bitstr_from_str_radix_rejects_empty_digits() {
+        assert_eq!(
+            Err(ParseDecimalError::InvalidDigit),
+            BitStr::from_str_radix("-_", 16)
+        );
+    }
+
+    #[test]
+    #[should_panic = "radix must be in range of 2..=36"]
+    fn bitstr_from_str_radix_37() {
+        let _ = BitStr::from_str_radix("1", 37);
+    }
+
+    #[test]
+    fn bitstr_from_str_prefixed() {
+        let mut neg_hex = BitStr::from("11111");
+        neg_hex.sign = Sign::Negative;
+
+        assert_eq!(Ok(neg_hex), BitStr::from_str_prefixed("-0x1F"));
+        assert_eq!(Ok(BitStr::from("111")), BitStr::from_str_prefixed("0o7"));
+        assert_eq!(Ok(BitStr::from("101")), BitStr::from_str_prefixed("0b101"));
+        assert_eq!(Ok(BitStr::from("1100")), BitStr::from_str_prefixed("12"));
+        assert_eq!(
+            Err(ParseDecimalError::InvalidDigit),
+            BitStr::from_str_prefixed("0b12")
+        );
+        assert_eq!(
+            Err(ParseDecimalError::InvalidDigit),
+            BitStr::from_str_prefixed("0x-1")
         );
     }
 
     #[test]
     fn bitstr_all_zeroes_zeroes() {
-        assert_eq!(true, BitStr::default().all_zeroes())
+        assert!(BitStr::default().all_zeroes())
     }
 
     #[test]
     fn bitstr_all_zeroes_one() {
-        assert_eq!(
-            false,
-            BitStr::from(format!("1{}", "0".repeat(95))).all_zeroes()
-        )
+        assert!(!BitStr::from(format!("1{}", "0".repeat(95))).all_zeroes())
     }
 
     #[test]
     fn bitstr_all_ones_ones() {
         let mut bstr = BitStr::default();
         bstr.invert(BitStr::LENGTH);
-        assert_eq!(true, bstr.all_ones())
+        assert!(bstr.all_ones())
     }
 
     #[test]
     fn bitstr_all_ones_zero() {
-        assert_eq!(
-            false,
-            BitStr::from(format!("0{}", "1".repeat(95))).all_ones()
-        )
+        assert!(!BitStr::from(format!("0{}", "1".repeat(95))).all_ones())
     }
 
     #[test]
@@ -506,21 +1075,60 @@ mod tests {
     }
 
     #[test]
-    fn bitstr_five_pow_one() {
+    fn bitstr_five_mul_pow10_one() {
         let mut bstr = BitStr::from("101");
-        bstr.pow(1);
+        bstr.mul_pow10(1).unwrap();
 
         assert_eq!(BitStr::from("110010"), bstr);
     }
 
     #[test]
-    fn bitstr_five_pow_three() {
+    fn bitstr_five_mul_pow10_three() {
         let mut bstr = BitStr::from("101");
-        bstr.pow(3);
+        bstr.mul_pow10(3).unwrap();
 
         assert_eq!(BitStr::from("1001110001000"), bstr);
     }
 
+    #[test]
+    fn bitstr_mul_pow10_zero_exp() {
+        let mut bstr = BitStr::from_str_radix("-1.01", 10).unwrap();
+        let expecting = bstr.clone();
+        bstr.mul_pow10(0).unwrap();
+
+        assert_eq!(expecting, bstr);
+    }
+
+    #[test]
+    fn bitstr_mul_pow10_large_exp() {
+        let mut bstr = BitStr::from("111");
+        bstr.mul_pow10(10).unwrap();
+        assert_eq!(BitStr::from_str_radix("70000000000", 10).unwrap(), bstr);
+
+        let mut bstr = BitStr::from("1");
+        bstr.mul_pow10(28).unwrap();
+        assert_eq!(
+            BitStr::from_str_radix("10000000000000000000000000000", 10).unwrap(),
+            bstr
+        );
+    }
+
+    #[test]
+    fn bitstr_mul_pow10_overflow() {
+        let mut bstr = BitStr::from("1000");
+        assert_eq!(Err(OverflowError), bstr.mul_pow10(28));
+        assert_eq!(BitStr::from("1000"), bstr);
+
+        assert_eq!(Err(OverflowError), bstr.mul_pow10(29));
+
+        let mut zero = BitStr::default();
+        assert_eq!(Ok(()), zero.mul_pow10(40));
+
+        let mut narrow = super::BitStr::<32>::from("1");
+        assert_eq!(Err(OverflowError), narrow.mul_pow10(10));
+        assert_eq!(Ok(()), narrow.mul_pow10(9));
+    }
+
     #[test]
     fn bitstr_add() {
         let left = BitStr::from("10");
@@ -536,4 +1144,520 @@ mod tests {
 
         assert_eq!(BitStr::default(), l.add_upto(&r, 3));
     }
+
+    #[test]
+    fn bitstr_widen() {
+        let mut bstr = BitStr::from_str_radix("-12.5", 10).unwrap();
+        bstr.bytes[0] = b'1';
+        let wide = super::BitStr::<128>::from(bstr.clone());
+
+        assert_eq!(Sign::Negative, wide.sign);
+        assert_eq!(1, wide.scale);
+        assert_eq!(&[b'0'; 32], &wide.bytes[..32]);
+        assert_eq!(&bstr.bytes[..], &wide.bytes[32..]);
+    }
+
+    #[test]
+    fn bitstr_narrow() {
+        let bstr = BitStr::from("1".repeat(32).as_str());
+        let narrow = super::BitStr::<32>::try_from(bstr).unwrap();
+
+        assert!(narrow.all_ones());
+        assert_eq!(
+            Err(OverflowError),
+            super::BitStr::<32>::try_from(BitStr::from("1".repeat(33).as_str()))
+        );
+
+        let mut wide = super::BitStr::<256>::default();
+        wide.bytes[0] = b'1';
+        assert_eq!(Err(OverflowError), super::BitStr::<96>::try_from(wide));
+    }
+
+    #[test]
+    fn bitstr_from_words() {
+        let five = super::BitStr::<64>::from_str_radix("5", 10).unwrap();
+
+        assert_eq!(
+            Ok(five.clone()),
+            super::BitStr::<64>::try_from(&[5, 0, 0][..])
+        );
+        assert_eq!(
+            five,
+            super::BitStr::<64>::new(Sign::Positive, 0, &[5, 0, 0])
+        );
+        assert_eq!(
+            Err(OverflowError),
+            super::BitStr::<64>::try_from(&[5, 0, 1][..])
+        );
+        assert_eq!(
+            Err(OverflowError),
+            super::BitStr::<8>::try_new(Sign::Negative, 1, &[256])
+        );
+
+        let bstr = BitStr::new(Sign::Negative, 2, &[-1, 0, 1]);
+        assert_eq!(Sign::Negative, bstr.sign);
+        assert_eq!(2, bstr.scale);
+        assert_eq!(33, bstr.count_ones());
+        assert!(bstr.get_bit(64));
+    }
+
+    #[test]
+    #[should_panic = "value does not fit in the target width"]
+    fn bitstr_new_overflow() {
+        super::BitStr::<32>::new(Sign::Positive, 0, &[0, 1]);
+    }
+
+    #[test]
+    fn bitstr_from_char_narrow() {
+        assert_eq!(
+            super::BitStr::<4>::from("1111"),
+            super::BitStr::<4>::from('F')
+        );
+        assert_eq!(
+            super::BitStr::<2>::from("11"),
+            super::BitStr::<2>::from('3')
+        );
+    }
+
+    #[test]
+    #[should_panic = "value does not fit in the target width"]
+    fn bitstr_from_char_overflow() {
+        let _ = super::BitStr::<2>::from('f');
+    }
+
+    #[test]
+    fn bitstr_parse_bare_prefix() {
+        assert_eq!(
+            Err(ParseDecimalError::InvalidDigit),
+            BitStr::from_str_prefixed("-0x")
+        );
+    }
+
+    #[test]
+    fn bitstr_narrow_parse_overflow() {
+        assert_eq!(
+            Err(ParseDecimalError::Overflow),
+            super::BitStr::<8>::from_str_radix("256", 10)
+        );
+        assert_eq!(
+            super::BitStr::<8>::from("11111111"),
+            super::BitStr::<8>::from_str_radix("255", 10).unwrap()
+        );
+    }
+
+    #[test]
+    fn bitstr_wide_parse() {
+        let wide = super::BitStr::<192>::from_str_radix("79228162514264337593543950336", 10);
+        assert_eq!(
+            Ok(super::BitStr::<192>::from(
+                format!("1{}", "0".repeat(96)).as_str()
+            )),
+            wide
+        );
+
+        let wide = super::BitStr::<192>::from_str_prefixed("-0x1_0000_0000_0000_0000_0000_0000.5");
+        assert_eq!(Err(ParseDecimalError::InvalidDigit), wide);
+
+        let wide =
+            super::BitStr::<128>::from_str_prefixed("-0xffffffffffffffffffffffffffffffff").unwrap();
+        assert_eq!(Sign::Negative, wide.sign);
+        assert_eq!(128, wide.count_ones());
+
+        assert_eq!(
+            Err(ParseDecimalError::Overflow),
+            super::BitStr::<96>::from_str_radix("79228162514264337593543950336", 10)
+        );
+    }
+
+    #[test]
+    fn bitstr_shl_shr() {
+        let bstr = BitStr::from("1011");
+
+        assert_eq!(BitStr::from("1011000"), bstr.clone() << 3);
+        assert_eq!(BitStr::from("10"), bstr.clone() >> 2);
+        assert_eq!(BitStr::default(), bstr.clone() >> 4);
+        assert_eq!(BitStr::default(), bstr.clone() << 96);
+        assert_eq!(BitStr::default(), bstr.clone() >> 200);
+
+        let mut bstr = bstr;
+        bstr <<= 94;
+        assert_eq!(BitStr::from("11"), bstr.clone() >> 94);
+        bstr >>= 1;
+        assert_eq!(1, bstr.leading_zeros());
+    }
+
+    #[test]
+    fn bitstr_bitops() {
+        let left = BitStr::from_str_radix("-1100", 2).unwrap();
+        let right = BitStr::from("1010");
+
+        let and = left.clone() & right.clone();
+        assert_eq!(Sign::Negative, and.sign);
+        assert_eq!(BitStr::from("1000").bytes, and.bytes);
+        assert_eq!(
+            BitStr::from("1110").bytes,
+            (left.clone() | right.clone()).bytes
+        );
+        assert_eq!(
+            BitStr::from("110").bytes,
+            (left.clone() ^ right.clone()).bytes
+        );
+
+        let mut acc = left.clone();
+        acc ^= left;
+        assert!(acc.all_zeroes());
+        acc |= right.clone();
+        acc &= BitStr::from("10");
+        assert_eq!(BitStr::from("10").bytes, acc.bytes);
+    }
+
+    #[test]
+    fn bitstr_not() {
+        assert!((!BitStr::default()).all_ones());
+        assert_eq!(94, (!BitStr::from("11")).count_ones());
+        assert_eq!(BitStr::from("101"), !!BitStr::from("101"));
+    }
+
+    #[test]
+    fn bitstr_bit_counts() {
+        let bstr = BitStr::from("1011000");
+
+        assert_eq!(89, bstr.leading_zeros());
+        assert_eq!(3, bstr.trailing_zeros());
+        assert_eq!(3, bstr.count_ones());
+        assert_eq!(Some(89), bstr.msbi());
+
+        let zero = BitStr::default();
+        assert_eq!(96, zero.leading_zeros());
+        assert_eq!(96, zero.trailing_zeros());
+        assert_eq!(0, zero.count_ones());
+        assert_eq!(None, zero.msbi());
+    }
+
+    /// `==` is numeric, so this also checks sign, scale and bits.
+    macro_rules! assert_repr_eq {
+        ($expecting:expr, $actual:expr) => {
+            let (expecting, actual) = ($expecting, $actual);
+
+            assert_eq!(expecting, actual);
+            assert_eq!(expecting.sign, actual.sign);
+            assert_eq!(expecting.scale, actual.scale);
+            assert_eq!(expecting.bytes, actual.bytes);
+        };
+    }
+
+    macro_rules! bitstr_arith_tc {
+        ($name:ident, $l:literal $op:tt $r:literal, $expecting:literal) => {
+            #[test]
+            fn $name() {
+                let l = BitStr::from_str_prefixed($l).unwrap();
+                let r = BitStr::from_str_prefixed($r).unwrap();
+                let expecting = BitStr::from_str_prefixed($expecting).unwrap();
+
+                assert_repr_eq!(expecting, l $op r);
+            }
+        };
+    }
+
+    bitstr_arith_tc!(bitstr_add_neg_larger_rhs, "1" + "-3", "-2");
+    bitstr_arith_tc!(bitstr_add_neg_larger_lhs, "-3" + "1", "-2");
+    bitstr_arith_tc!(bitstr_add_neg_to_zero, "0" + "-1", "-1");
+    bitstr_arith_tc!(bitstr_add_cancel, "-3" + "3", "0");
+    bitstr_arith_tc!(bitstr_sub, "10" - "3", "7");
+    bitstr_arith_tc!(bitstr_sub_below_zero, "3" - "10", "-7");
+    bitstr_arith_tc!(bitstr_sub_neg, "-3" - "-10", "7");
+    bitstr_arith_tc!(bitstr_sub_neg_pos, "-3" - "10", "-13");
+    bitstr_arith_tc!(bitstr_sub_self, "5" - "5", "0");
+    bitstr_arith_tc!(bitstr_add_mixed_scale, "4.5" + "1", "5.5");
+    bitstr_arith_tc!(bitstr_add_mixed_scale_rhs, "1" + "0.25", "1.25");
+    bitstr_arith_tc!(bitstr_add_mixed_scale_sign, "-0.5" + "2", "1.5");
+    bitstr_arith_tc!(bitstr_sub_mixed_scale, "4.5" - "1", "3.5");
+    bitstr_arith_tc!(bitstr_sub_mixed_scale_rhs, "1" - "0.25", "0.75");
+    bitstr_arith_tc!(bitstr_sub_mixed_scale_to_zero, "1.5" - "1.50", "0");
+    bitstr_arith_tc!(bitstr_mul, "5" * "3", "15");
+    bitstr_arith_tc!(bitstr_mul_neg, "-5" * "3", "-15");
+    bitstr_arith_tc!(bitstr_mul_neg_neg, "-5" * "-3", "15");
+    bitstr_arith_tc!(bitstr_mul_zero, "-5" * "0", "0");
+    bitstr_arith_tc!(bitstr_mul_scale, "1.1" * "1.1", "1.21");
+    bitstr_arith_tc!(
+        bitstr_mul_max,
+        "0xffff_ffff_ffff_ffff_ffff_ffff" * "1",
+        "0xffff_ffff_ffff_ffff_ffff_ffff"
+    );
+
+    #[test]
+    #[should_panic = "overflow"]
+    fn bitstr_add_align_overflow() {
+        let _ = BitStr::from("1".repeat(95).as_str()) + BitStr::from_str_radix("0.1", 10).unwrap();
+    }
+
+    #[test]
+    #[should_panic = "underflow"]
+    fn bitstr_sub_align_underflow() {
+        let _ = BitStr::from_str_radix("0.1", 10).unwrap()
+            - BitStr::from_str_radix("39614081257132168796771975167", 10).unwrap();
+    }
+
+    #[test]
+    #[should_panic = "overflow"]
+    fn bitstr_mul_overflow() {
+        let _ = BitStr::from("1".repeat(50).as_str()) * BitStr::from("1".repeat(50).as_str());
+    }
+
+    #[test]
+    #[should_panic = "underflow"]
+    fn bitstr_mul_underflow() {
+        let _ = BitStr::from("1".repeat(95).as_str()) * BitStr::from_str_radix("-100", 2).unwrap();
+    }
+
+    macro_rules! bitstr_div_rem_tc {
+        ($name:ident, $l:literal / $r:literal, $quotient:literal, $remainder:literal) => {
+            #[test]
+            fn $name() {
+                let l = BitStr::from_str_prefixed($l).unwrap();
+                let r = BitStr::from_str_prefixed($r).unwrap();
+                let (quotient, remainder) = l.div_rem(&r);
+
+                assert_repr_eq!(BitStr::from_str_prefixed($quotient).unwrap(), quotient);
+                assert_repr_eq!(BitStr::from_str_prefixed($remainder).unwrap(), remainder);
+            }
+        };
+    }
+
+    bitstr_div_rem_tc!(bitstr_div_rem, "17" / "5", "3", "2");
+    bitstr_div_rem_tc!(bitstr_div_rem_exact, "20" / "5", "4", "0");
+    bitstr_div_rem_tc!(bitstr_div_rem_smaller, "4" / "5", "0", "4");
+    bitstr_div_rem_tc!(bitstr_div_rem_neg_lhs, "-17" / "5", "-3", "-2");
+    bitstr_div_rem_tc!(bitstr_div_rem_neg_rhs, "17" / "-5", "-3", "2");
+    bitstr_div_rem_tc!(bitstr_div_rem_neg_both, "-17" / "-5", "3", "-2");
+    bitstr_div_rem_tc!(bitstr_div_rem_scale, "1.7" / "5", "0.3", "0.2");
+    bitstr_div_rem_tc!(
+        bitstr_div_rem_top_bit,
+        "0xffff_ffff_ffff_ffff_ffff_ffff" / "0x8000_0000_0000_0000_0000_0001",
+        "1",
+        "0x7fff_ffff_ffff_ffff_ffff_fffe"
+    );
+    bitstr_div_rem_tc!(
+        bitstr_div_rem_max_by_ten,
+        "79228162514264337593543950335" / "10",
+        "7922816251426433759354395033",
+        "5"
+    );
+
+    #[test]
+    #[should_panic = "division by zero"]
+    fn bitstr_div_rem_by_zero() {
+        let _ = BitStr::from("1").div_rem(&BitStr::default());
+    }
+
+    macro_rules! bitstr_cmp_tc {
+        ($name:ident, $method:ident, $l:literal, $r:literal, $expecting:ident) => {
+            #[test]
+            fn $name() {
+                let l = BitStr::from_str_prefixed($l).unwrap();
+                let r = BitStr::from_str_prefixed($r).unwrap();
+
+                assert_eq!(core::cmp::Ordering::$expecting, l.$method(&r));
+                assert_eq!(core::cmp::Ordering::$expecting.reverse(), r.$method(&l));
+            }
+        };
+    }
+
+    bitstr_cmp_tc!(bitstr_cmp_value_sign, cmp_value, "-5", "3", Less);
+    bitstr_cmp_tc!(bitstr_cmp_value_neg, cmp_value, "-5", "-3", Less);
+    bitstr_cmp_tc!(bitstr_cmp_value_scale, cmp_value, "1.5", "2", Less);
+    bitstr_cmp_tc!(
+        bitstr_cmp_value_trailing_zeros,
+        cmp_value,
+        "1.00",
+        "1",
+        Equal
+    );
+    bitstr_cmp_tc!(bitstr_cmp_value_neg_zero, cmp_value, "-0.0", "0", Equal);
+    bitstr_cmp_tc!(bitstr_cmp_value_small, cmp_value, "-0.001", "0", Less);
+    bitstr_cmp_tc!(bitstr_cmp_magnitude, cmp_magnitude, "-5", "3", Greater);
+    bitstr_cmp_tc!(
+        bitstr_cmp_magnitude_scale,
+        cmp_magnitude,
+        "0.5",
+        "-0.49",
+        Greater
+    );
+    bitstr_cmp_tc!(
+        bitstr_cmp_magnitude_rescale_overflow,
+        cmp_magnitude,
+        "79228162514264337593543950335",
+        "7.9228162514264337593543950335",
+        Greater
+    );
+    bitstr_cmp_tc!(bitstr_cmp_ord_scale, cmp, "1.0", "1", Equal);
+    bitstr_cmp_tc!(bitstr_cmp_ord_neg_zero, cmp, "-0", "0", Equal);
+    bitstr_cmp_tc!(bitstr_cmp_ord_value, cmp, "1.0", "2", Less);
+
+    #[test]
+    fn bitstr_cmp_rescale_far() {
+        let mut tiny = BitStr::from("1");
+        tiny.scale = 200;
+        let zero = BitStr {
+            scale: i32::MAX,
+            ..BitStr::default()
+        };
+
+        assert!(tiny > BitStr::default());
+        assert!(tiny < BitStr::from("1"));
+        assert_eq!(
+            core::cmp::Ordering::Equal,
+            zero.cmp_value(&BitStr::default())
+        );
+    }
+
+    #[test]
+    fn bitstr_sort_dedup() {
+        let mut values: Vec<BitStr> = ["3", "-1", "0.5", "-0", "1.0", "0", "1", "-1", "0.50"]
+            .iter()
+            .map(|s| BitStr::from_str_prefixed(s).unwrap())
+            .collect();
+        values.sort();
+        values.dedup();
+
+        let expecting: Vec<BitStr> = ["-1", "0", "0.5", "1", "3"]
+            .iter()
+            .map(|s| BitStr::from_str_prefixed(s).unwrap())
+            .collect();
+        assert_eq!(expecting, values);
+    }
+
+    macro_rules! bitstr_from_str_tc {
+        ($name:ident, $value:expr, $expecting:expr) => {
+            #[test]
+            fn $name() {
+                assert_eq!($expecting, $value.parse::<BitStr>());
+            }
+        };
+    }
+
+    bitstr_from_str_tc!(bitstr_from_str, "101", Ok(BitStr::from("101")));
+    bitstr_from_str_tc!(
+        bitstr_from_str_signed,
+        "-0b1_01",
+        Ok(BitStr::from_str_radix("-101", 2).unwrap())
+    );
+    bitstr_from_str_tc!(bitstr_from_str_plus, "+0B11", Ok(BitStr::from("11")));
+    bitstr_from_str_tc!(
+        bitstr_from_str_leading_zeros,
+        &format!("{}1", "0".repeat(200)),
+        Ok(BitStr::from("1"))
+    );
+    bitstr_from_str_tc!(
+        bitstr_from_str_full,
+        &"1".repeat(96),
+        Ok(!BitStr::default())
+    );
+    bitstr_from_str_tc!(
+        bitstr_from_str_too_long,
+        &"1".repeat(97),
+        Err(ParseDecimalError::Overflow)
+    );
+    bitstr_from_str_tc!(bitstr_from_str_empty, "", Err(ParseDecimalError::Empty));
+    bitstr_from_str_tc!(
+        bitstr_from_str_sign_only,
+        "-",
+        Err(ParseDecimalError::InvalidDigit)
+    );
+    bitstr_from_str_tc!(
+        bitstr_from_str_prefix_only,
+        "0b_",
+        Err(ParseDecimalError::InvalidDigit)
+    );
+    bitstr_from_str_tc!(
+        bitstr_from_str_digit,
+        "102",
+        Err(ParseDecimalError::InvalidDigit)
+    );
+    bitstr_from_str_tc!(
+        bitstr_from_str_space,
+        "1 0",
+        Err(ParseDecimalError::InvalidDigit)
+    );
+    bitstr_from_str_tc!(
+        bitstr_from_str_invalid_before_overflow,
+        &format!("x{}", "1".repeat(97)),
+        Err(ParseDecimalError::InvalidDigit)
+    );
+
+    #[test]
+    fn bitstr_from_str_narrow() {
+        assert_eq!(
+            Err(ParseDecimalError::Overflow),
+            "1_0000_0000".parse::<super::BitStr<8>>()
+        );
+        assert!("1111_1111".parse::<super::BitStr<8>>().unwrap().all_ones());
+    }
+
+    #[test]
+    fn bitstr_get_set_clear_bit() {
+        let mut bstr = BitStr::from("1001");
+
+        assert!(bstr.get_bit(0));
+        assert!(!bstr.get_bit(1));
+        assert!(bstr[3]);
+        assert!(!bstr[95]);
+
+        bstr.set_bit(95);
+        bstr.set_bit(1);
+        bstr.clear_bit(0);
+        assert_eq!(Some(0), bstr.msbi());
+        assert_eq!(1, bstr.trailing_zeros());
+        assert_eq!(3, bstr.count_ones());
+    }
+
+    #[test]
+    #[should_panic = "bit index 96 is out of range of 0..96"]
+    fn bitstr_get_bit_out_of_range() {
+        BitStr::default().get_bit(96);
+    }
+
+    #[test]
+    fn bitstr_iter_bits() {
+        let bstr = BitStr::from("110");
+
+        assert_eq!(96, bstr.iter_bits().len());
+        assert_eq!(
+            vec![false, true, true, false],
+            bstr.iter_bits().take(4).collect::<Vec<_>>()
+        );
+        assert_eq!(Some(2), bstr.iter_bits().rposition(|bit| bit));
+        assert_eq!(
+            bstr.leading_zeros() as usize,
+            bstr.iter_bits().rev().take_while(|bit| !bit).count()
+        );
+    }
+
+    #[test]
+    fn bitstr_from_decstr_rounds_once() {
+        let expecting = BitStr::from_str_radix("1234567890123456789012345678.3", 10).unwrap();
+
+        assert_eq!(
+            Ok(expecting.clone()),
+            BitStr::try_from(DecStr::from("1234567890123456789012345678.349"))
+        );
+        assert_eq!(
+            Ok(expecting),
+            BitStr::try_from(DecStr::from(
+                "1234567890123456789012345678.34999999999999999999999999999"
+            ))
+        );
+    }
+
+    #[test]
+    fn bitstr_from_decstr_overflow() {
+        assert_eq!(
+            Err(OverflowError),
+            BitStr::try_from(DecStr::from("1".repeat(33).as_str()))
+        );
+        assert_eq!(
+            Err(OverflowError),
+            BitStr::try_from(DecStr::from("-79228162514264337593543950335.5"))
+        );
+    }
 }
@@ -1,94 +1,320 @@
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
 use super::{BitStr, Sign};
 
-use std::cmp::Ordering::*;
+use core::cmp::Ordering::*;
 
-const DECSTR_LEN: usize = 64;
+/// How the last kept digit is chosen when digits are dropped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rounding {
+    /// Ties go to the even digit, as in `S21Decimal` arithmetic.
+    HalfEven,
+    HalfAwayFromZero,
+    TowardZero,
+}
 
-#[derive(Debug, Clone, PartialEq)]
+/// Arbitrary-precision decimal scratchpad with one ASCII digit per byte, most
+/// significant first. Meant for debugging and as a reference for `S21Decimal`
+/// results.
+///
+/// `bytes` grows as needed and carries no leading zeros, so zero has no
+/// digits at all. Comparisons are numeric: `1.0 == 1.00` and `-0 == 0`.
+#[derive(Debug, Clone)]
 pub struct DecStr {
     pub sign: Sign,
     pub scale: i32,
-    pub bytes: [u8; DECSTR_LEN],
+    pub bytes: Vec<u8>,
 }
 
 impl DecStr {
-    const LENGTH: usize = DECSTR_LEN;
-
     pub fn add(&self, other: &Self) -> Self {
         let mut result = self.clone();
 
-        Self::_add(&mut result, &other);
+        Self::_add(&mut result, other);
 
         result
     }
 
     pub fn add_mut(&mut self, other: &Self) {
-        Self::_add(self, &other);
+        Self::_add(self, other);
     }
 
-    pub fn integral(&self) -> String {
-        self.to_string()
-            .trim_start_matches('0')
-            .split_at(29 - self.scale as usize)
-            .0
-            .to_owned()
+    pub fn sub(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+
+        Self::_add(&mut result, &-other.clone());
+
+        result
     }
 
-    pub fn fraction(&self) -> String {
-        self.to_string()
-            .trim_start_matches('0')
-            .split_at(29 - self.scale as usize)
-            .1
-            .to_owned()
+    pub fn sub_mut(&mut self, other: &Self) {
+        Self::_add(self, &-other.clone());
+    }
+
+    /// The integer part, truncated towards zero, with scale 0.
+    ///
+    /// Together with [`DecStr::fraction`] it adds up to `self`; a part that
+    /// is zero is positive.
+    pub fn integral(&self) -> Self {
+        self.cut_fractional().with_sign(&self.sign)
+    }
+
+    /// The fractional part, keeping the scale of `self`, or 0 when the scale
+    /// is not positive.
+    pub fn fraction(&self) -> Self {
+        let scale = self.scale.max(0);
+        let start = self.bytes.len().saturating_sub(scale as usize);
+        let mut result = DecStr::from(&self.bytes[start..]);
+        result.scale = scale;
+
+        result.with_sign(&self.sign)
+    }
+
+    fn with_sign(mut self, sign: &Sign) -> Self {
+        self.sign = if self.is_zero() {
+            Sign::Positive
+        } else {
+            sign.clone()
+        };
+
+        self
     }
 
     pub fn rescale(&self, scale: i32) -> Self {
         let mut res = self.clone();
 
         if scale > 0 {
-            res.bytes.rotate_left(scale as usize);
+            if !res.bytes.is_empty() {
+                res.bytes.resize(res.bytes.len() + scale as usize, b'0');
+            }
             res.scale += scale;
         } else if scale < 0 {
-            let mut num_of_elem = -scale;
-            res.bytes
-                .iter_mut()
-                .rev()
-                .take_while(|_| {
-                    num_of_elem -= 1;
-                    num_of_elem >= 0
-                })
-                .for_each(|c| *c = 48);
-            res.bytes.rotate_right(-scale as usize);
+            let len = res
+                .bytes
+                .len()
+                .saturating_sub(scale.unsigned_abs() as usize);
+            res.bytes.truncate(len);
             res.scale += scale;
         }
 
         res
     }
 
+    /// Rounds half to even to an integer, same as `round_to(0)`.
     pub fn banker_round(&self) -> Self {
-        if self.scale > 0 {
-            return match (self.bytes[Self::LENGTH - self.scale as usize - 1] - 48) % 2 {
-                0 => self.cut_fractional(),
-                _ => self.cut_fractional().add(&DecStr::from("1")),
-            };
+        self.round_to(0)
+    }
+
+    /// Rounds half to even down to `scale` fractional digits. Values that
+    /// already have `scale` digits or fewer are returned unchanged.
+    pub fn round_to(&self, scale: i32) -> Self {
+        self.round_with(scale, Rounding::HalfEven)
+    }
+
+    /// Like [`DecStr::round_to`], with the given rounding strategy.
+    pub fn round_with(&self, scale: i32, rounding: Rounding) -> Self {
+        if scale >= self.scale {
+            return self.clone().with_sign(&self.sign);
+        }
+
+        let cut = (self.scale - scale) as usize;
+        let mut result = self.rescale(scale - self.scale);
+
+        let round_up = match (rounding, self.digit(cut - 1)) {
+            (Rounding::TowardZero, _) => false,
+            (Rounding::HalfAwayFromZero, digit) => digit >= b'5',
+            (Rounding::HalfEven, b'6'..=b'9') => true,
+            (Rounding::HalfEven, b'5') => {
+                self.bytes.iter().rev().take(cut - 1).any(|b| *b != b'0')
+                    || (self.digit(cut) - 48) % 2 == 1
+            }
+            (Rounding::HalfEven, _) => false,
+        };
+
+        if round_up {
+            let mut ulp = DecStr::from("1");
+            ulp.sign = self.sign.clone();
+            ulp.scale = scale;
+            result.add_mut(&ulp);
+        }
+
+        result.with_sign(&self.sign)
+    }
+
+    /// Schoolbook multiplication. The scale of the product is the sum of the
+    /// scales.
+    pub fn mul(&self, other: &Self) -> Self {
+        let mut digits = vec![0u32; self.bytes.len() + other.bytes.len()];
+
+        for (i, l) in self.bytes.iter().rev().enumerate() {
+            if *l == b'0' {
+                continue;
+            }
+
+            for (j, r) in other.bytes.iter().rev().enumerate() {
+                digits[i + j] += (l - 48) as u32 * (r - 48) as u32;
+            }
+        }
+
+        let mut carry = 0;
+
+        for digit in digits.iter_mut() {
+            *digit += carry;
+            carry = *digit / 10;
+            *digit %= 10;
+        }
+
+        let bytes = digits
+            .iter()
+            .rev()
+            .map(|d| *d as u8 + 48)
+            .collect::<Vec<_>>();
+        let mut result = DecStr::from(bytes.as_slice());
+        result.sign = Self::product_sign(self, other);
+        result.scale = self.scale + other.scale;
+        result
+    }
+
+    /// Long division to `precision` fractional digits, rounding the last one
+    /// with `rounding`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `other` is zero.
+    pub fn div(&self, other: &Self, precision: u32, rounding: Rounding) -> Self {
+        if other.is_empty() {
+            panic!("division by zero");
+        }
+
+        // At least one digit beyond `precision`, and more when the operand
+        // scales demand them, so that rounding sees every dropped digit.
+        let scale = (precision as i32 + 1).max(self.scale - other.scale);
+        let shift = (other.scale + scale - self.scale) as usize;
+        let divisor = other.magnitude();
+        let mut remainder = DecStr::default();
+        let mut digits = String::new();
+
+        for digit in self.bytes.iter().chain(core::iter::repeat_n(&b'0', shift)) {
+            if !remainder.bytes.is_empty() || *digit != b'0' {
+                remainder.bytes.push(*digit);
+            }
+
+            let mut quotient = b'0';
+
+            while remainder.cmp_magnitude(&divisor) != Less {
+                remainder.sub_mut(&divisor);
+                quotient += 1;
+            }
+
+            digits.push(quotient as char);
         }
 
-        self.clone()
+        // A trailing sticky digit marks an inexact quotient, so that ties are
+        // only rounded as ties when the remainder is zero.
+        digits.push(if remainder.is_empty() { '0' } else { '1' });
+
+        let sign = Self::product_sign(self, other);
+        let mut result = DecStr::from(digits.as_bytes());
+        result.sign = sign.clone();
+        result.scale = scale + 1;
+        result
+            .round_with(precision as i32, rounding)
+            .with_sign(&sign)
+    }
+
+    fn product_sign(lhs: &Self, rhs: &Self) -> Sign {
+        if lhs.sign == rhs.sign || lhs.is_empty() || rhs.is_empty() {
+            Sign::Positive
+        } else {
+            Sign::Negative
+        }
+    }
+
+    fn magnitude(&self) -> Self {
+        Self {
+            sign: Sign::Positive,
+            scale: 0,
+            bytes: self.bytes.clone(),
+        }
+    }
+
+    /// Compares the digits as integers, ignoring sign and scale.
+    fn cmp_magnitude(&self, other: &Self) -> core::cmp::Ordering {
+        self.bytes
+            .len()
+            .cmp(&other.bytes.len())
+            .then_with(|| self.bytes.cmp(&other.bytes))
+    }
+
+    /// The ASCII digit `position` places from the right, `'0'` past the
+    /// most significant one.
+    fn digit(&self, position: usize) -> u8 {
+        self.bytes
+            .len()
+            .checked_sub(position + 1)
+            .map_or(b'0', |index| self.bytes[index])
+    }
+
+    fn trim_leading_zeros(&mut self) {
+        let zeros = self.bytes.iter().take_while(|b| **b == b'0').count();
+        self.bytes.drain(..zeros);
     }
 
     pub fn len(&self) -> usize {
-        self.bytes.iter().skip_while(|c| **c == 48).count()
+        self.bytes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    pub fn abs(&self) -> Self {
+        Self {
+            sign: Sign::Positive,
+            ..self.clone()
+        }
+    }
+
+    fn is_negative(&self) -> bool {
+        self.sign == Sign::Negative && !self.is_zero()
+    }
+
+    /// Compares the magnitudes after bringing both to the larger scale.
+    fn cmp_rescaled(&self, other: &Self) -> core::cmp::Ordering {
+        let scale = self.scale.max(other.scale);
+
+        self.rescale(scale - self.scale)
+            .cmp_magnitude(&other.rescale(scale - other.scale))
     }
 
     fn cut_fractional(&self) -> Self {
         self.clone().rescale(-self.scale)
     }
 
+    /// Adds with scale alignment. With opposite signs the smaller magnitude
+    /// is subtracted from the larger one, whose sign the result takes; equal
+    /// magnitudes give positive zero.
     fn _add(dest: &mut Self, rhs: &Self) {
         use Sign::*;
+
+        let mut rhs = rhs.clone();
+
+        match dest.scale.cmp(&rhs.scale) {
+            Less => *dest = dest.rescale(rhs.scale - dest.scale),
+            Equal => (),
+            Greater => rhs = rhs.rescale(dest.scale - rhs.scale),
+        }
+
         match (&dest.sign, &rhs.sign) {
             (Positive, Positive) | (Negative, Negative) => {
-                Self::calc_add(dest, rhs, |l, r, carry| {
+                Self::calc_add(dest, &rhs, |l, r, carry| {
                     let mut sum = l + r + *carry as u8;
 
                     if *carry > 0 {
@@ -103,71 +329,152 @@ impl DecStr {
                     sum + 48
                 })
             }
-            _ => Self::calc_add(dest, rhs, |l, r, carry| {
-                let mut sum = l as i8 - r as i8 - *carry as i8;
-
-                if *carry > 0 {
-                    *carry -= 1;
+            _ => {
+                if dest.cmp_magnitude(&rhs) == Less {
+                    core::mem::swap(dest, &mut rhs);
                 }
 
-                if sum < 0 {
-                    *carry += 1;
-                    sum += 10;
-                }
+                Self::calc_add(dest, &rhs, |l, r, carry| {
+                    let mut sum = l as i8 - r as i8 - *carry as i8;
+
+                    if *carry > 0 {
+                        *carry -= 1;
+                    }
+
+                    if sum < 0 {
+                        *carry += 1;
+                        sum += 10;
+                    }
 
-                sum as u8 + 48
-            }),
+                    sum as u8 + 48
+                });
+
+                if dest.is_empty() {
+                    dest.sign = Positive;
+                }
+            }
         }
     }
 
+    /// Applies `calcs` digit by digit from the right, over one more digit
+    /// than the longer operand so that a final carry is kept.
     fn calc_add<F>(dest: &mut Self, rhs: &Self, calcs: F)
     where
         F: Fn(u8, u8, &mut i32) -> u8,
     {
-        let mut rhs = rhs.clone();
+        let width = dest.bytes.len().max(rhs.bytes.len()) + 1;
         let mut carry = 0;
+        let mut bytes = vec![b'0'; width];
 
-        match dest.scale.cmp(&rhs.scale) {
-            Less => *dest = dest.rescale(rhs.scale - dest.scale),
-            Equal => (),
-            Greater => rhs = rhs.rescale(dest.scale - rhs.scale),
+        for (i, byte) in bytes.iter_mut().rev().enumerate() {
+            *byte = calcs(dest.digit(i) - 48, rhs.digit(i) - 48, &mut carry);
         }
 
-        for i in (0..DECSTR_LEN).rev() {
-            let left = dest.bytes[i] - 48;
-            let right = rhs.bytes[i] - 48;
+        dest.bytes = bytes;
+        dest.trim_leading_zeros();
+    }
+}
 
-            dest.bytes[i] = calcs(left, right, &mut carry);
-        }
+impl core::ops::Neg for DecStr {
+    type Output = Self;
+
+    fn neg(mut self) -> Self::Output {
+        self.sign = match self.sign {
+            Sign::Positive => Sign::Negative,
+            Sign::Negative => Sign::Positive,
+        };
+
+        self
+    }
+}
+
+impl core::ops::Add for DecStr {
+    type Output = Self;
+
+    fn add(mut self, rhs: Self) -> Self::Output {
+        self.add_mut(&rhs);
+        self
     }
 }
 
-impl std::fmt::Display for DecStr {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let string = self
+impl core::ops::Sub for DecStr {
+    type Output = Self;
+
+    fn sub(mut self, rhs: Self) -> Self::Output {
+        self.sub_mut(&rhs);
+        self
+    }
+}
+
+impl core::ops::Mul for DecStr {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        DecStr::mul(&self, &rhs)
+    }
+}
+
+impl core::ops::AddAssign for DecStr {
+    fn add_assign(&mut self, rhs: Self) {
+        self.add_mut(&rhs);
+    }
+}
+
+impl core::ops::SubAssign for DecStr {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.sub_mut(&rhs);
+    }
+}
+
+impl core::fmt::Display for DecStr {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let scale = self.scale.max(0) as usize;
+        let mut string = self
             .bytes
-            .as_slice()
             .iter()
             .map(|b| *b as char)
-            .fold(String::new(), |mut acc, c| {
-                acc.push(c);
-                acc
-            })
+            .collect::<String>()
             .trim_start_matches('0')
             .to_owned();
 
-        let split = string.split_at(string.len() - self.scale as usize);
+        if self.scale < 0 && !string.is_empty() {
+            string.push_str(&"0".repeat(self.scale.unsigned_abs() as usize));
+        }
 
-        write!(
-            f,
-            "{}{}",
-            if split.0.is_empty() { "0" } else { split.0 },
-            if split.1.is_empty() {
-                String::new()
-            } else {
-                format!(".{}", split.1)
-            }
-        )
+        if string.len() <= scale {
+            string.insert_str(0, &"0".repeat(scale + 1 - string.len()));
+        }
+
+        if scale > 0 {
+            string.insert(string.len() - scale, '.');
+        }
+
+        f.pad_integral(self.sign == Sign::Positive, "", &string)
+    }
+}
+
+impl PartialEq for DecStr {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Equal
+    }
+}
+
+impl Eq for DecStr {}
+
+impl Ord for DecStr {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        match (self.is_negative(), other.is_negative()) {
+            (false, false) => self.cmp_rescaled(other),
+            (true, true) => other.cmp_rescaled(self),
+            (false, true) => Greater,
+            (true, false) => Less,
+        }
+    }
+}
+
+impl PartialOrd for DecStr {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
     }
 }
 
@@ -176,13 +483,13 @@ impl Default for DecStr {
         Self {
             sign: Sign::Positive,
             scale: 0,
-            bytes: [48; DECSTR_LEN],
+            bytes: Vec::new(),
         }
     }
 }
 
-impl From<BitStr> for DecStr {
-    fn from(bs: BitStr) -> Self {
+impl<const N: usize> From<BitStr<N>> for DecStr {
+    fn from(bs: BitStr<N>) -> Self {
         let mut res = DecStr::default();
         let mut idx = 0;
 
@@ -233,32 +540,19 @@ impl From<&str> for DecStr {
 
 impl From<&[u8]> for DecStr {
     fn from(s: &[u8]) -> Self {
-        let mut ds = DecStr::default();
-
-        if s.len() == Self::LENGTH {
-            s.iter()
-                .enumerate()
-                .for_each(|(i, byte)| ds.bytes[i] = *byte);
-        } else if s.len() < Self::LENGTH {
-            let diff = Self::LENGTH - s.len();
-            s.iter()
-                .enumerate()
-                .for_each(|(i, byte)| ds.bytes[i + diff] = *byte);
-        } else {
-            panic!(
-                "Byte slice is too long. Expected {} got {}",
-                Self::LENGTH,
-                s.len()
-            );
-        }
+        let mut ds = DecStr {
+            bytes: s.to_vec(),
+            ..DecStr::default()
+        };
 
+        ds.trim_leading_zeros();
         ds
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{DecStr, Sign};
+    use crate::{DecStr, Rounding, S21Decimal, Sign};
 
     macro_rules! from_str_assert {
         (from: $from:literal, expect: {
@@ -271,6 +565,9 @@ mod tests {
             expecting.sign = $sign;
             expecting.scale = $scale;
             assert_eq!(expecting, ds);
+            assert_eq!(expecting.sign, ds.sign);
+            assert_eq!(expecting.scale, ds.scale);
+            assert_eq!(expecting.bytes, ds.bytes);
         };
     }
 
@@ -279,6 +576,41 @@ mod tests {
             let l = DecStr::from(stringify!($lhs));
             let r = DecStr::from(stringify!($rhs));
             assert_eq!(DecStr::from(stringify!($expect)), l.add(&r));
+            assert_eq!(stringify!($expect), l.add(&r).to_string());
+        };
+    }
+
+    macro_rules! decstr_mul_assert {
+        ($lhs:literal * $rhs:literal = $expect:literal) => {
+            let l = DecStr::from(stringify!($lhs));
+            let r = DecStr::from(stringify!($rhs));
+            assert_eq!(DecStr::from(stringify!($expect)), l.mul(&r));
+            assert_eq!(stringify!($expect), l.mul(&r).to_string());
+        };
+    }
+
+    macro_rules! decstr_div_assert {
+        ($lhs:literal / $rhs:literal, $precision:literal, $rounding:ident = $expect:literal) => {
+            let l = DecStr::from(stringify!($lhs));
+            let r = DecStr::from(stringify!($rhs));
+            assert_eq!(
+                DecStr::from(stringify!($expect)),
+                l.div(&r, $precision, Rounding::$rounding)
+            );
+            assert_eq!(
+                stringify!($expect),
+                l.div(&r, $precision, Rounding::$rounding).to_string()
+            );
+        };
+    }
+
+    macro_rules! decstr_sub_assert {
+        ($lhs:literal - $rhs:literal = $expect:literal) => {
+            let l = DecStr::from(stringify!($lhs));
+            let r = DecStr::from(stringify!($rhs));
+            assert_eq!(DecStr::from(stringify!($expect)), l.sub(&r));
+            assert_eq!(stringify!($expect), l.sub(&r).to_string());
+            assert_eq!(DecStr::from(stringify!($expect)), l - r);
         };
     }
 
@@ -354,6 +686,48 @@ mod tests {
         );
     }
 
+    #[test]
+    fn decstr_display_neg() {
+        assert_eq!("-4.5", DecStr::from("-4.5").to_string());
+    }
+
+    #[test]
+    fn decstr_display_leading_zeroes_in_fraction() {
+        assert_eq!("-0.005", DecStr::from("-0.005").to_string());
+    }
+
+    #[test]
+    fn decstr_banker_round() {
+        assert_eq!("3", DecStr::from("3.4").banker_round().to_string());
+        assert_eq!("3", DecStr::from("2.51").banker_round().to_string());
+        assert_eq!("2", DecStr::from("2.5").banker_round().to_string());
+        assert_eq!("4", DecStr::from("3.5").banker_round().to_string());
+        assert_eq!("-2", DecStr::from("-1.5").banker_round().to_string());
+        assert_eq!("7", DecStr::from("7").banker_round().to_string());
+    }
+
+    #[test]
+    fn decstr_round_to_half_even() {
+        assert_eq!("1.00", DecStr::from("1.005").round_to(2).to_string());
+        assert_eq!("1.02", DecStr::from("1.015").round_to(2).to_string());
+        assert_eq!("-1.01", DecStr::from("-1.0051").round_to(2).to_string());
+    }
+
+    #[test]
+    fn decstr_round_to_zero_is_positive() {
+        let rounded = DecStr::from("-0.4").round_to(0);
+
+        assert_eq!(Sign::Positive, rounded.sign);
+        assert_eq!("0", rounded.to_string());
+        assert_eq!("0.0", DecStr::from("-0.04").round_to(1).to_string());
+        assert_eq!(
+            "0",
+            DecStr::from("-0.9")
+                .round_with(0, Rounding::TowardZero)
+                .to_string()
+        );
+    }
+
     #[test]
     fn decstr_add_left_one_right_one() {
         decstr_add_assert!(1 + 1 = 2);
@@ -368,4 +742,362 @@ mod tests {
     fn decstr_add_left_45_right_neg_one() {
         decstr_add_assert!(45 + -1 = 44);
     }
+
+    #[test]
+    fn decstr_add_left_one_right_neg_45() {
+        decstr_add_assert!(1 + -45 = -44);
+    }
+
+    #[test]
+    fn decstr_add_left_neg_45_right_one() {
+        decstr_add_assert!(-45 + 1 = -44);
+    }
+
+    #[test]
+    fn decstr_add_left_neg_one_right_45() {
+        decstr_add_assert!(-1 + 45 = 44);
+    }
+
+    #[test]
+    fn decstr_add_opposite_equal_magnitude() {
+        decstr_add_assert!(-4.5 + 4.5 = 0.0);
+    }
+
+    #[test]
+    fn decstr_add_mixed_sign_scale() {
+        decstr_add_assert!(0.01 + -4.5 = -4.49);
+    }
+
+    #[test]
+    fn decstr_sub_left_45_right_one() {
+        decstr_sub_assert!(45 - 1 = 44);
+    }
+
+    #[test]
+    fn decstr_sub_left_one_right_45() {
+        decstr_sub_assert!(1 - 45 = -44);
+    }
+
+    #[test]
+    fn decstr_sub_left_neg_one_right_45() {
+        decstr_sub_assert!(-1 - 45 = -46);
+    }
+
+    #[test]
+    fn decstr_sub_left_neg_one_right_neg_45() {
+        decstr_sub_assert!(-1 - -45 = 44);
+    }
+
+    #[test]
+    fn decstr_sub_borrow_across_scale() {
+        decstr_sub_assert!(100 - 0.001 = 99.999);
+    }
+
+    #[test]
+    fn decstr_sub_self() {
+        decstr_sub_assert!(-7.25 - -7.25 = 0.00);
+    }
+
+    #[test]
+    fn decstr_neg() {
+        assert_eq!(DecStr::from("-4.5"), -DecStr::from("4.5"));
+        assert_eq!(DecStr::from("4.5"), -DecStr::from("-4.5"));
+    }
+
+    #[test]
+    fn decstr_add_sub_assign() {
+        let mut ds = DecStr::from("10");
+        ds += DecStr::from("-12.5");
+        assert_eq!(DecStr::from("-2.5"), ds);
+
+        ds -= DecStr::from("-2.5");
+        assert_eq!(DecStr::from("0.0"), ds);
+        assert_eq!(DecStr::from("1.5"), DecStr::from("1") + DecStr::from("0.5"));
+    }
+
+    #[test]
+    fn decstr_mul() {
+        decstr_mul_assert!(12 * 34 = 408);
+        decstr_mul_assert!(99 * 99 = 9801);
+        decstr_mul_assert!(1.5 * -0.2 = -0.30);
+        decstr_mul_assert!(-1.5 * -2 = 3.0);
+        decstr_mul_assert!(-1.5 * 0 = 0.0);
+    }
+
+    #[test]
+    fn decstr_mul_max_decimal() {
+        decstr_mul_assert!(
+            79228162514264337593543950335 * 79228162514264337593543950335 =
+                6277101735386680763835789423049210091073826769276946612225
+        );
+    }
+
+    #[test]
+    fn decstr_mul_grows() {
+        let nines = DecStr::from("9".repeat(40).as_str());
+        let expecting = format!("{}8{}1", "9".repeat(39), "0".repeat(39));
+
+        assert_eq!(DecStr::from(expecting.as_str()), nines.mul(&nines));
+        assert_eq!(80, nines.mul(&nines).len());
+    }
+
+    #[test]
+    fn decstr_add_grows() {
+        let nines = DecStr::from("9".repeat(64).as_str());
+        let sum = nines.add(&DecStr::from("1"));
+
+        assert_eq!(65, sum.len());
+        assert_eq!(format!("1{}", "0".repeat(64)), sum.to_string());
+    }
+
+    #[test]
+    fn decstr_from_long_input() {
+        let digits = format!("-{}.{}", "1".repeat(70), "2".repeat(30));
+        let ds = DecStr::from(digits.as_str());
+
+        assert_eq!(100, ds.len());
+        assert_eq!(30, ds.scale);
+        assert_eq!(digits, ds.to_string());
+        assert_eq!(DecStr::from("1"), DecStr::from("0001".as_bytes()));
+    }
+
+    #[test]
+    fn decstr_div_wide_operands() {
+        let dividend = DecStr::from(format!("1{}", "0".repeat(80)).as_str());
+        let divisor = DecStr::from(format!("3{}", "0".repeat(70)).as_str());
+
+        decstr_div_assert!(1 / 3, 0, HalfEven = 0);
+        assert_eq!(
+            DecStr::from("3333333333.33"),
+            dividend.div(&divisor, 2, Rounding::HalfEven)
+        );
+        assert_eq!(
+            DecStr::from(format!("0.{}1", "0".repeat(79)).as_str()),
+            DecStr::from("1").div(&dividend, 80, Rounding::HalfEven)
+        );
+    }
+
+    #[test]
+    fn decstr_div() {
+        decstr_div_assert!(1 / 3, 5, HalfEven = 0.33333);
+        decstr_div_assert!(2 / 3, 5, HalfEven = 0.66667);
+        decstr_div_assert!(2 / 3, 5, TowardZero = 0.66666);
+        decstr_div_assert!(7.5 / 2.5, 0, HalfEven = 3);
+        decstr_div_assert!(1 / 0.04, 2, HalfEven = 25.00);
+        decstr_div_assert!(-1 / 4, 2, HalfEven = -0.25);
+        decstr_div_assert!(-1 / -4, 2, HalfEven = 0.25);
+        decstr_div_assert!(0 / -4, 1, HalfEven = 0.0);
+    }
+
+    #[test]
+    fn decstr_div_rounding_ties() {
+        decstr_div_assert!(1 / 8, 2, HalfEven = 0.12);
+        decstr_div_assert!(3 / 8, 2, HalfEven = 0.38);
+        decstr_div_assert!(1 / 8, 2, HalfAwayFromZero = 0.13);
+        decstr_div_assert!(-1 / 8, 2, HalfAwayFromZero = -0.13);
+        decstr_div_assert!(-1 / 8, 2, TowardZero = -0.12);
+        decstr_div_assert!(5 / 2, 0, HalfEven = 2);
+    }
+
+    #[test]
+    fn decstr_div_finer_dividend() {
+        decstr_div_assert!(0.12345 / 1, 2, HalfEven = 0.12);
+        decstr_div_assert!(0.125 / 1, 2, HalfEven = 0.12);
+        decstr_div_assert!(0.1250001 / 1, 2, HalfEven = 0.13);
+        decstr_div_assert!(0.1250001 / 10, 3, HalfEven = 0.013);
+    }
+
+    #[test]
+    fn decstr_div_sticky_remainder() {
+        decstr_div_assert!(0.2500001 / 2, 1, HalfEven = 0.1);
+        decstr_div_assert!(1.0000001 / 8, 2, HalfEven = 0.13);
+    }
+
+    #[test]
+    fn decstr_div_max_decimal() {
+        decstr_div_assert!(
+            79228162514264337593543950335 / 10,
+            28,
+            HalfEven = 7922816251426433759354395033.5000000000000000000000000000
+        );
+    }
+
+    #[test]
+    fn decstr_div_zero_quotient_is_positive() {
+        let quotient = DecStr::from("1").div(&DecStr::from("-3"), 0, Rounding::HalfEven);
+
+        assert_eq!(Sign::Positive, quotient.sign);
+        assert_eq!("0", quotient.to_string());
+        assert_eq!(
+            "0.00",
+            DecStr::from("-1")
+                .div(&DecStr::from("1000"), 2, Rounding::TowardZero)
+                .to_string()
+        );
+    }
+
+    #[test]
+    #[should_panic = "division by zero"]
+    fn decstr_div_by_zero() {
+        DecStr::from("1").div(&DecStr::from("0.00"), 2, Rounding::HalfEven);
+    }
+
+    #[test]
+    fn decstr_mul_operator() {
+        assert_eq!(DecStr::from("-6"), DecStr::from("2") * DecStr::from("-3"));
+    }
+
+    #[test]
+    fn decstr_eq_numeric() {
+        assert_eq!(DecStr::from("1.0"), DecStr::from("1.00"));
+        assert_eq!(DecStr::from("-0.0"), DecStr::from("0"));
+        assert_ne!(DecStr::from("1.01"), DecStr::from("1.1"));
+        assert_ne!(DecStr::from("-1"), DecStr::from("1"));
+    }
+
+    #[test]
+    fn decstr_ord() {
+        let mut values = ["3", "-1.5", "0.25", "-0", "-10", "0.3", "1"]
+            .iter()
+            .map(|s| DecStr::from(*s))
+            .collect::<Vec<_>>();
+        values.sort();
+
+        assert_eq!(
+            vec!["-10", "-1.5", "-0", "0.25", "0.3", "1", "3"],
+            values.iter().map(|ds| ds.to_string()).collect::<Vec<_>>()
+        );
+        assert!(DecStr::from("-0.01") < DecStr::from("-0.001"));
+        assert!(DecStr::from(format!("{}.5", "9".repeat(70)).as_str()) > DecStr::from("1"));
+    }
+
+    #[test]
+    fn decstr_is_zero_abs_max_min() {
+        assert!(DecStr::from("-0.000").is_zero());
+        assert!(!DecStr::from("0.001").is_zero());
+        assert_eq!("4.50", DecStr::from("-4.50").abs().to_string());
+
+        let l = DecStr::from("-4.5");
+        let r = DecStr::from("0.1");
+        assert_eq!("0.1", l.clone().max(r.clone()).to_string());
+        assert_eq!("-4.5", l.min(r).to_string());
+    }
+
+    /// Reproducible pseudo-random values covering zero, negative zero, long
+    /// digit strings and scales beyond the number of digits.
+    fn samples() -> impl Iterator<Item = DecStr> {
+        let mut state = 0x2545_f491_4f6c_dd1d_u64;
+        let mut next = move |bound: u64| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state % bound
+        };
+
+        (0..1000).map(move |_| {
+            let len = next(45) as usize;
+            let digits = (0..len).map(|_| b'0' + next(10) as u8).collect::<Vec<_>>();
+            let mut ds = DecStr::from(digits.as_slice());
+            ds.scale = next(len as u64 + 4) as i32;
+            ds.sign = if next(2) == 0 {
+                Sign::Positive
+            } else {
+                Sign::Negative
+            };
+
+            ds
+        })
+    }
+
+    #[test]
+    fn decstr_integral_fraction() {
+        let split = |s: &str| {
+            let ds = DecStr::from(s);
+            (ds.integral().to_string(), ds.fraction().to_string())
+        };
+
+        assert_eq!(("123".into(), "0.456".into()), split("123.456"));
+        assert_eq!(("-123".into(), "-0.456".into()), split("-123.456"));
+        assert_eq!(("0".into(), "-0.5".into()), split("-0.5"));
+        assert_eq!(("-7".into(), "0".into()), split("-7"));
+        assert_eq!(("0".into(), "0".into()), split("0"));
+        assert_eq!(("0".into(), "0.00".into()), split("-0.00"));
+        assert_eq!(("0".into(), "0.007".into()), split("0.007"));
+        assert_eq!(("5".into(), "0.000".into()), split("5.000"));
+
+        let long = format!("{}.{}", "9".repeat(40), "1".repeat(35));
+        assert_eq!(
+            ("9".repeat(40), format!("0.{}", "1".repeat(35))),
+            split(&long)
+        );
+    }
+
+    #[test]
+    fn decstr_integral_fraction_negative_scale() {
+        let ds = DecStr {
+            scale: -2,
+            ..DecStr::from("-12")
+        };
+
+        assert_eq!("-1200", ds.to_string());
+        assert_eq!("-1200", ds.integral().to_string());
+        assert_eq!("0", ds.fraction().to_string());
+    }
+
+    #[test]
+    fn decstr_display_zero_with_scale() {
+        assert_eq!("0.00", DecStr::from("0.00").to_string());
+        assert_eq!("-0.000", DecStr::from("-0.000").to_string());
+        assert_eq!("0", DecStr::default().to_string());
+        assert_eq!(
+            "0",
+            DecStr {
+                scale: -3,
+                ..DecStr::default()
+            }
+            .to_string()
+        );
+        assert_eq!("   0.10", format!("{:>7}", DecStr::from("0.10")));
+        assert_eq!("+0.10", format!("{:+}", DecStr::from("0.10")));
+    }
+
+    #[test]
+    fn decstr_display_round_trip() {
+        for ds in samples() {
+            let parsed = DecStr::from(ds.to_string().as_str());
+
+            assert_eq!(ds.sign, parsed.sign, "{ds}");
+            assert_eq!(ds.scale, parsed.scale, "{ds}");
+            assert_eq!(ds.bytes, parsed.bytes, "{ds}");
+        }
+    }
+
+    #[test]
+    fn decstr_integral_fraction_round_trip() {
+        let one = DecStr::from("1");
+
+        for ds in samples() {
+            let integral = ds.integral();
+            let fraction = ds.fraction();
+
+            assert_eq!(ds, integral.add(&fraction), "{ds}");
+            assert_eq!(0, integral.scale, "{ds}");
+            assert_eq!(ds.scale, fraction.scale, "{ds}");
+            assert!(fraction.abs() < one, "{ds}");
+            assert!(integral.abs() <= ds.abs(), "{ds}");
+            assert!(integral.is_zero() || integral.sign == ds.sign, "{ds}");
+            assert!(fraction.is_zero() || fraction.sign == ds.sign, "{ds}");
+            assert_eq!(integral, integral.integral(), "{ds}");
+            assert!(fraction.integral().is_zero(), "{ds}");
+        }
+    }
+
+    #[test]
+    fn decstr_display_matches_decimal() {
+        for ds in samples().filter(|ds| ds.len() <= 28 && ds.scale <= 28) {
+            let decimal: S21Decimal = ds.to_string().parse().unwrap();
+
+            assert_eq!(ds.to_string(), decimal.to_string(), "{ds:?}");
+        }
+    }
 }
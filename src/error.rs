@@ -0,0 +1,64 @@
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecimalError {
+    ScaleOutOfRange(i32),
+    ReservedBitsSet(i32),
+}
+
+impl core::fmt::Display for DecimalError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::ScaleOutOfRange(scale) => {
+                write!(f, "scale {scale} is out of range of 0..=28")
+            }
+            Self::ReservedBitsSet(bits) => {
+                write!(f, "reserved bits are set in flags word: {bits:#010x}")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DecimalError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseDecimalError {
+    Empty,
+    InvalidDigit,
+    InvalidExponent,
+    Overflow,
+    ScaleOverflow,
+}
+
+impl ParseDecimalError {
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Empty => "cannot parse decimal from empty string",
+            Self::InvalidDigit => "invalid digit found in string",
+            Self::InvalidExponent => "invalid exponent found in string",
+            Self::Overflow => "number too large to fit in 96 bits",
+            Self::ScaleOverflow => "scale exceeds 28 fractional digits",
+        }
+    }
+}
+
+impl core::fmt::Display for ParseDecimalError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseDecimalError {}
+
+/// A value did not fit in the width of the target `BitStr`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OverflowError;
+
+impl core::fmt::Display for OverflowError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("value does not fit in the target width")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for OverflowError {}
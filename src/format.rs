@@ -0,0 +1,548 @@
+use crate::mantissa::{count_digits, div_pow10, Digits};
+use crate::S21Decimal;
+
+use core::fmt::{self, Write};
+
+/// Formats a decimal in engineering notation, where the exponent is always a
+/// multiple of 3 (`125e-9`, `1.5e3`). Created by [`S21Decimal::engineering`].
+pub struct Engineering<'a>(&'a S21Decimal);
+
+impl S21Decimal {
+    pub fn engineering(&self) -> Engineering<'_> {
+        Engineering(self)
+    }
+
+    /// Renders the 96-bit mantissa in the given radix using lowercase digits,
+    /// prefixed with `-` for negative values. The scale is not rendered, so
+    /// the output is the unscaled coefficient: `-4.5` in radix 16 is `-2d`.
+    ///
+    /// The `Binary`, `Octal`, `LowerHex` and `UpperHex` impls follow the same
+    /// rules and additionally honour `#`, `+`, width and zero-padding.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `radix` is not in the range from 2 to 36.
+    #[cfg(feature = "alloc")]
+    pub fn to_string_radix(&self, radix: u32) -> alloc::string::String {
+        assert!(
+            (2..=36).contains(&radix),
+            "radix must be in range of 2..=36, got {radix}"
+        );
+
+        let digits = Digits::with_radix(self.mantissa(), radix, false);
+        let sign = if self.is_negative() { "-" } else { "" };
+
+        alloc::format!("{sign}{}", digits.as_str())
+    }
+
+    fn fmt_exp(&self, f: &mut fmt::Formatter<'_>, step: u32, e: u8) -> fmt::Result {
+        let (mantissa, scale, exp) = exp_parts(self.mantissa(), self.scale(), step, f.precision());
+        let (body, zeros) = render(mantissa, scale, f.precision());
+
+        let mut suffix = Buf::<16>::default();
+        suffix.push(e);
+        if exp < 0 {
+            suffix.push(b'-');
+        }
+        suffix.push_str(Digits::new(exp.unsigned_abs() as u128).as_str());
+
+        pad(
+            f,
+            self.is_negative(),
+            "",
+            body.as_str(),
+            zeros,
+            suffix.as_str(),
+        )
+    }
+}
+
+/// Fixed-capacity ASCII buffer for assembling output on the stack.
+struct Buf<const N: usize> {
+    bytes: [u8; N],
+    len: usize,
+}
+
+impl<const N: usize> Default for Buf<N> {
+    fn default() -> Self {
+        Self {
+            bytes: [0; N],
+            len: 0,
+        }
+    }
+}
+
+impl<const N: usize> Buf<N> {
+    fn push(&mut self, byte: u8) {
+        self.bytes[self.len] = byte;
+        self.len += 1;
+    }
+
+    fn push_str(&mut self, str: &str) {
+        str.bytes().for_each(|byte| self.push(byte));
+    }
+
+    fn as_str(&self) -> &str {
+        core::str::from_utf8(&self.bytes[..self.len]).unwrap()
+    }
+}
+
+/// Renders `mantissa * 10^-scale`. Returns the text along with the number of
+/// zeros that still have to be appended to reach `precision` fractional
+/// digits.
+fn render(mantissa: u128, scale: u32, precision: Option<usize>) -> (Buf<64>, usize) {
+    let digits = Digits::new(mantissa);
+    let digits = digits.as_str();
+    let scale = scale as usize;
+    let mut body = Buf::default();
+
+    if digits.len() <= scale {
+        body.push_str("0.");
+        (digits.len()..scale).for_each(|_| body.push(b'0'));
+        body.push_str(digits);
+    } else {
+        let (integral, fraction) = digits.split_at(digits.len() - scale);
+        body.push_str(integral);
+
+        if !fraction.is_empty() {
+            body.push(b'.');
+            body.push_str(fraction);
+        }
+    }
+
+    match precision {
+        Some(precision) if precision > scale => {
+            if scale == 0 {
+                body.push(b'.');
+            }
+
+            (body, precision - scale)
+        }
+        _ => (body, 0),
+    }
+}
+
+/// Writes `body`, `zeros` zeros and `suffix` with the sign and, for `#`, the
+/// prefix in front, honouring width, fill, alignment and zero-padding the
+/// same way `Formatter::pad_integral` does.
+fn pad(
+    f: &mut fmt::Formatter<'_>,
+    negative: bool,
+    prefix: &str,
+    body: &str,
+    zeros: usize,
+    suffix: &str,
+) -> fmt::Result {
+    let sign = match (negative, f.sign_plus()) {
+        (true, _) => "-",
+        (false, true) => "+",
+        (false, false) => "",
+    };
+    let prefix = if f.alternate() { prefix } else { "" };
+    let len = sign.len() + prefix.len() + body.len() + zeros + suffix.len();
+    let padding = f.width().unwrap_or(0).saturating_sub(len);
+
+    let (before, after) = match f.align() {
+        _ if f.sign_aware_zero_pad() => (0, 0),
+        Some(fmt::Alignment::Left) => (0, padding),
+        Some(fmt::Alignment::Center) => (padding / 2, padding - padding / 2),
+        _ => (padding, 0),
+    };
+
+    let fill = f.fill();
+    (0..before).try_for_each(|_| f.write_char(fill))?;
+    f.write_str(sign)?;
+    f.write_str(prefix)?;
+
+    if f.sign_aware_zero_pad() {
+        (0..padding).try_for_each(|_| f.write_char('0'))?;
+    }
+
+    f.write_str(body)?;
+    (0..zeros).try_for_each(|_| f.write_char('0'))?;
+    f.write_str(suffix)?;
+    (0..after).try_for_each(|_| f.write_char(fill))
+}
+
+/// Rounds half to even to `precision` fractional digits, or drops trailing
+/// fractional zeros when there is no precision.
+fn round(mantissa: u128, scale: u32, precision: Option<usize>) -> (u128, u32) {
+    match precision {
+        Some(precision) if precision < scale as usize => (
+            div_pow10(mantissa, scale - precision as u32),
+            precision as u32,
+        ),
+        Some(_) => (mantissa, scale),
+        None => {
+            let mut mantissa = mantissa;
+            let mut scale = scale;
+
+            while scale > 0 && mantissa.is_multiple_of(10) {
+                mantissa /= 10;
+                scale -= 1;
+            }
+
+            (mantissa, scale)
+        }
+    }
+}
+
+/// Splits `mantissa * 10^-scale` into a rounded mantissa with its own scale
+/// and an exponent that is a multiple of `step`, so that the mantissa has
+/// between 1 and `step` integer digits.
+fn exp_parts(mantissa: u128, scale: i32, step: u32, precision: Option<usize>) -> (u128, u32, i32) {
+    if mantissa == 0 {
+        return (0, 0, 0);
+    }
+
+    let step = step as i32;
+    let mut exp = count_digits(mantissa) as i32 - 1 - scale;
+    exp -= exp.rem_euclid(step);
+
+    let (mantissa, scale) = match scale + exp {
+        scale if scale < 0 => (mantissa * 10u128.pow(-scale as u32), 0),
+        scale => (mantissa, scale as u32),
+    };
+    let (mut mantissa, mut scale) = round(mantissa, scale, precision);
+
+    if count_digits(mantissa) as i32 - scale as i32 > step {
+        exp += step;
+        (mantissa, scale) = round(mantissa, scale + step as u32, precision);
+    }
+
+    (mantissa, scale, exp)
+}
+
+impl fmt::Display for S21Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (mantissa, scale) = match f.precision() {
+            Some(_) => round(self.mantissa(), self.scale() as u32, f.precision()),
+            None => (self.mantissa(), self.scale() as u32),
+        };
+        let (body, zeros) = render(mantissa, scale, f.precision());
+
+        pad(f, self.is_negative(), "", body.as_str(), zeros, "")
+    }
+}
+
+impl fmt::LowerExp for S21Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_exp(f, 1, b'e')
+    }
+}
+
+impl fmt::UpperExp for S21Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_exp(f, 1, b'E')
+    }
+}
+
+macro_rules! impl_fmt_radix {
+    ($trait:ident, $prefix:literal, $radix:literal, $upper:literal) => {
+        impl fmt::$trait for S21Decimal {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                let digits = Digits::with_radix(self.mantissa(), $radix, $upper);
+
+                pad(f, self.is_negative(), $prefix, digits.as_str(), 0, "")
+            }
+        }
+    };
+}
+
+impl_fmt_radix!(Binary, "0b", 2, false);
+impl_fmt_radix!(Octal, "0o", 8, false);
+impl_fmt_radix!(LowerHex, "0x", 16, false);
+impl_fmt_radix!(UpperHex, "0x", 16, true);
+
+impl fmt::Display for Engineering<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::LowerExp::fmt(self, f)
+    }
+}
+
+impl fmt::LowerExp for Engineering<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt_exp(f, 3, b'e')
+    }
+}
+
+impl fmt::UpperExp for Engineering<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt_exp(f, 3, b'E')
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    macro_rules! decimal_fmt_tc {
+        ($name:ident { format: $fmt:literal, value: $value:literal, expect: $expect:literal }) => {
+            #[test]
+            fn $name() {
+                let decimal = S21Decimal::from_str_radix(stringify!($value), 10).unwrap();
+
+                assert_eq!($expect, format!($fmt, decimal));
+            }
+        };
+    }
+
+    decimal_fmt_tc!(decimal_fmt_zero {
+        format: "{}",
+        value: 0,
+        expect: "0"
+    });
+
+    decimal_fmt_tc!(decimal_fmt_neg_scale_3 {
+        format: "{}",
+        value: -0.005,
+        expect: "-0.005"
+    });
+
+    decimal_fmt_tc!(decimal_fmt_max {
+        format: "{}",
+        value: 79_228_162_514_264_337_593_543_950_335,
+        expect: "79228162514264337593543950335"
+    });
+
+    decimal_fmt_tc!(decimal_fmt_precision_rounds_half_even {
+        format: "{:.2}",
+        value: 1.005,
+        expect: "1.00"
+    });
+
+    decimal_fmt_tc!(decimal_fmt_precision_rounds_up {
+        format: "{:.2}",
+        value: -1.015,
+        expect: "-1.02"
+    });
+
+    decimal_fmt_tc!(decimal_fmt_precision_zero {
+        format: "{:.0}",
+        value: 2.5,
+        expect: "2"
+    });
+
+    decimal_fmt_tc!(decimal_fmt_precision_pads_zeroes {
+        format: "{:.3}",
+        value: 4.5,
+        expect: "4.500"
+    });
+
+    decimal_fmt_tc!(decimal_fmt_precision_pads_integer {
+        format: "{:.2}",
+        value: 45,
+        expect: "45.00"
+    });
+
+    decimal_fmt_tc!(decimal_fmt_plus_sign {
+        format: "{:+}",
+        value: 4.5,
+        expect: "+4.5"
+    });
+
+    decimal_fmt_tc!(decimal_fmt_width_right_align {
+        format: "{:>6}",
+        value: -4.5,
+        expect: "  -4.5"
+    });
+
+    decimal_fmt_tc!(decimal_fmt_width_fill_center {
+        format: "{:*^9.1}",
+        value: 4.55,
+        expect: "***4.6***"
+    });
+
+    decimal_fmt_tc!(decimal_fmt_zero_pad {
+        format: "{:+08.2}",
+        value: 1.5,
+        expect: "+0001.50"
+    });
+
+    decimal_fmt_tc!(decimal_fmt_lower_exp {
+        format: "{:e}",
+        value: 0.000_000_125,
+        expect: "1.25e-7"
+    });
+
+    decimal_fmt_tc!(decimal_fmt_upper_exp {
+        format: "{:E}",
+        value: -300_000,
+        expect: "-3E5"
+    });
+
+    decimal_fmt_tc!(decimal_fmt_exp_zero {
+        format: "{:e}",
+        value: 0.00,
+        expect: "0e0"
+    });
+
+    decimal_fmt_tc!(decimal_fmt_exp_trailing_zeroes {
+        format: "{:e}",
+        value: 1.2500,
+        expect: "1.25e0"
+    });
+
+    decimal_fmt_tc!(decimal_fmt_exp_precision_rounds {
+        format: "{:.1e}",
+        value: 1.25,
+        expect: "1.2e0"
+    });
+
+    decimal_fmt_tc!(decimal_fmt_exp_precision_carry {
+        format: "{:.2e}",
+        value: 9.996,
+        expect: "1.00e1"
+    });
+
+    decimal_fmt_tc!(decimal_fmt_exp_precision_pads_zeroes {
+        format: "{:.3e}",
+        value: 45,
+        expect: "4.500e1"
+    });
+
+    decimal_fmt_tc!(decimal_fmt_exp_max {
+        format: "{:e}",
+        value: 79_228_162_514_264_337_593_543_950_335,
+        expect: "7.9228162514264337593543950335e28"
+    });
+
+    decimal_fmt_tc!(decimal_fmt_exp_width {
+        format: "{:>+10.1e}",
+        value: 4500,
+        expect: "    +4.5e3"
+    });
+
+    macro_rules! decimal_fmt_eng_tc {
+        ($name:ident { format: $fmt:literal, value: $value:literal, expect: $expect:literal }) => {
+            #[test]
+            fn $name() {
+                let decimal = S21Decimal::from_str_radix(stringify!($value), 10).unwrap();
+
+                assert_eq!($expect, format!($fmt, decimal.engineering()));
+            }
+        };
+    }
+
+    decimal_fmt_eng_tc!(decimal_fmt_eng_negative_exp {
+        format: "{}",
+        value: 0.000_000_125,
+        expect: "125e-9"
+    });
+
+    decimal_fmt_eng_tc!(decimal_fmt_eng_positive_exp {
+        format: "{:E}",
+        value: -1500,
+        expect: "-1.5E3"
+    });
+
+    decimal_fmt_eng_tc!(decimal_fmt_eng_small_integer {
+        format: "{}",
+        value: 42,
+        expect: "42e0"
+    });
+
+    decimal_fmt_eng_tc!(decimal_fmt_eng_precision_carry {
+        format: "{:.1}",
+        value: 999.96,
+        expect: "1.0e3"
+    });
+
+    decimal_fmt_eng_tc!(decimal_fmt_eng_fraction {
+        format: "{:.2}",
+        value: 0.012_345,
+        expect: "12.34e-3"
+    });
+
+    #[test]
+    fn decimal_engineering_reexported() {
+        let decimal = S21Decimal::from(1500);
+        let eng: crate::Engineering<'_> = decimal.engineering();
+
+        assert_eq!("1.5e3", format!("{eng}"));
+    }
+
+    decimal_fmt_tc!(decimal_fmt_binary {
+        format: "{:b}",
+        value: 4.5,
+        expect: "101101"
+    });
+
+    decimal_fmt_tc!(decimal_fmt_binary_alternate_zero_pad {
+        format: "{:#010b}",
+        value: -5,
+        expect: "-0b0000101"
+    });
+
+    decimal_fmt_tc!(decimal_fmt_octal {
+        format: "{:#o}",
+        value: 8,
+        expect: "0o10"
+    });
+
+    decimal_fmt_tc!(decimal_fmt_lower_hex {
+        format: "{:x}",
+        value: -4.5,
+        expect: "-2d"
+    });
+
+    decimal_fmt_tc!(decimal_fmt_upper_hex_max {
+        format: "{:#X}",
+        value: 79_228_162_514_264_337_593_543_950_335,
+        expect: "0xFFFFFFFFFFFFFFFFFFFFFFFF"
+    });
+
+    decimal_fmt_tc!(decimal_fmt_upper_hex_plus_width {
+        format: "{:>+6X}",
+        value: 255,
+        expect: "   +FF"
+    });
+
+    decimal_fmt_tc!(decimal_fmt_left_align {
+        format: "{:_<8.1}",
+        value: -4.55,
+        expect: "-4.6____"
+    });
+
+    decimal_fmt_tc!(decimal_fmt_large_precision {
+        format: "{:.40}",
+        value: 0.5,
+        expect: "0.5000000000000000000000000000000000000000"
+    });
+
+    decimal_fmt_tc!(decimal_fmt_exp_zero_pad {
+        format: "{:010.2e}",
+        value: -0.000_125,
+        expect: "-001.25e-4"
+    });
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn decimal_to_string_radix() {
+        let decimal = S21Decimal::from_str_radix("-4.5", 10).unwrap();
+
+        assert_eq!("-101101", decimal.to_string_radix(2));
+        assert_eq!("-2d", decimal.to_string_radix(16));
+        assert_eq!("-19", decimal.to_string_radix(36));
+        assert_eq!("0", S21Decimal::default().to_string_radix(7));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn decimal_to_string_radix_round_trip() {
+        let decimal =
+            S21Decimal::from_str_radix("79_228_162_514_264_337_593_543_950_335", 10).unwrap();
+
+        (2..=36).for_each(|radix| {
+            let string = decimal.to_string_radix(radix);
+            assert_eq!(decimal, S21Decimal::from_str_radix(&string, radix).unwrap());
+        });
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    #[should_panic = "radix must be in range of 2..=36"]
+    fn decimal_to_string_radix_1() {
+        S21Decimal::default().to_string_radix(1);
+    }
+}
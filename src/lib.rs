@@ -1,43 +1,148 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "alloc")]
+pub use bits::Bits;
 use bits::*;
+#[cfg(feature = "alloc")]
 pub use bitstr::*;
+#[cfg(feature = "alloc")]
 pub use decstr::*;
+pub use error::*;
+pub use format::Engineering;
+use mantissa::*;
 
 mod bits;
+#[cfg(feature = "alloc")]
 mod bitstr;
+#[cfg(feature = "alloc")]
 mod decstr;
+mod error;
+mod format;
+mod macros;
+mod mantissa;
 mod ops;
+mod parse;
 
 const EXP_MASK: i32 = 0b00000000111111110000000000000000;
+const RESERVED_MASK: i32 = 0b01111111000000001111111111111111;
+const MAX_SCALE: i32 = 28;
 
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub enum Sign {
     Positive,
     Negative,
 }
 
-#[derive(Debug, PartialEq)]
+/// A 128-bit decimal: a 96-bit unsigned mantissa, a sign and a scale of
+/// 0..=28. The layout is `#[repr(C)]` and matches the C `s21_decimal` struct
+/// and the words of .NET's `decimal.GetBits`: `bits[0..3]` hold the mantissa
+/// from low to high, `bits[3]` holds the scale in bits 16–23 and the sign in
+/// bit 31.
+#[repr(C)]
+#[derive(Debug, Default, PartialEq, Clone, Copy)]
 pub struct S21Decimal {
     bits: [i32; 4],
 }
 
+const _: () = assert!(core::mem::size_of::<S21Decimal>() == 16);
+const _: () = assert!(core::mem::align_of::<S21Decimal>() == core::mem::align_of::<i32>());
+
 impl S21Decimal {
+    pub const ZERO: Self = Self::from_mantissa(0, Sign::Positive, 0);
+    pub const ONE: Self = Self::from_mantissa(1, Sign::Positive, 0);
+    pub const NEGATIVE_ONE: Self = Self::from_mantissa(1, Sign::Negative, 0);
+    pub const MAX: Self = Self::from_mantissa(MAX_MANTISSA, Sign::Positive, 0);
+    pub const MIN: Self = Self::from_mantissa(MAX_MANTISSA, Sign::Negative, 0);
+    /// The smallest positive value, `1e-28`.
+    pub const EPSILON: Self = Self::from_mantissa(1, Sign::Positive, MAX_SCALE);
+
+    /// # Panics
+    ///
+    /// Panics if `scale` is not in the range from 0 to 28. See
+    /// [`S21Decimal::try_new`] for a non-panicking version.
     pub fn new(integer: i32, scale: i32) -> Self {
-        let mut decimal = Self::from(integer);
-        decimal.set_scale(scale);
+        Self::try_new(integer, scale).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    pub const fn try_new(integer: i32, scale: i32) -> Result<Self, DecimalError> {
+        match check_scale(scale) {
+            Ok(()) => Ok(Self::from_integer(integer, scale)),
+            Err(err) => Err(err),
+        }
+    }
 
-        decimal
+    /// Builds a decimal from the three 32-bit words of the mantissa, the sign
+    /// and the scale, mirroring .NET's `Decimal(int, int, int, bool, byte)`.
+    pub const fn from_parts(
+        lo: i32,
+        mid: i32,
+        hi: i32,
+        negative: bool,
+        scale: u8,
+    ) -> Result<Self, DecimalError> {
+        let sign = if negative { i32::MIN } else { 0 };
+
+        match check_scale(scale as i32) {
+            Ok(()) => Ok(Self {
+                bits: [lo, mid, hi, sign | (scale as i32) << 16],
+            }),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Builds a decimal from the `bits` layout of the C `s21_decimal` struct.
+    /// The scale must not exceed 28 and the reserved bits 0–15 and 24–30 of
+    /// `bits[3]` must be zero.
+    pub const fn from_raw_bits(bits: [i32; 4]) -> Result<Self, DecimalError> {
+        if bits[3] & RESERVED_MASK != 0 {
+            return Err(DecimalError::ReservedBitsSet(bits[3] & RESERVED_MASK));
+        }
+
+        match check_scale((bits[3] & EXP_MASK) >> 16) {
+            Ok(()) => Ok(Self { bits }),
+            Err(err) => Err(err),
+        }
     }
 
-    pub fn from_str_radix(str: &str, radix: u32) -> Self {
-        BitStr::from_str_radix(str, radix).into()
+    pub const fn to_raw_bits(&self) -> [i32; 4] {
+        self.bits
+    }
+
+    pub(crate) const fn from_mantissa(mantissa: u128, sign: Sign, scale: i32) -> Self {
+        let flags = match sign {
+            Sign::Positive => 0,
+            Sign::Negative => i32::MIN,
+        };
+
+        Self {
+            bits: [
+                mantissa as u32 as i32,
+                (mantissa >> 32) as u32 as i32,
+                (mantissa >> 64) as u32 as i32,
+                flags | scale << 16,
+            ],
+        }
+    }
+
+    const fn from_integer(integer: i32, scale: i32) -> Self {
+        let sign = if integer < 0 {
+            Sign::Negative
+        } else {
+            Sign::Positive
+        };
+
+        Self::from_mantissa(integer.unsigned_abs() as u128, sign, scale)
     }
 
     pub const fn is_negative(&self) -> bool {
         get_bit(self.bits[3], 31) == 1
     }
 
-    fn set_negative(&mut self) {
-        set_bit(&mut self.bits[3], 31);
+    pub const fn is_zero(&self) -> bool {
+        self.mantissa() == 0
     }
 
     const fn sign(&self) -> Sign {
@@ -48,6 +153,7 @@ impl S21Decimal {
         }
     }
 
+    #[cfg_attr(not(feature = "alloc"), allow(dead_code))]
     fn set_sign(&mut self, sign: Sign) {
         match sign {
             Sign::Positive => unset_bit(&mut self.bits[3], 31),
@@ -59,82 +165,217 @@ impl S21Decimal {
         (self.bits[3] & EXP_MASK) >> 16
     }
 
+    #[cfg_attr(not(feature = "alloc"), allow(dead_code))]
     fn set_scale(&mut self, exp: i32) {
+        if let Err(err) = check_scale(exp) {
+            panic!("{err}");
+        }
+
         let sign = self.sign();
         self.bits[3] = exp << 16;
         self.set_sign(sign);
     }
 
+    /// Raises the scale to `scale`, multiplying the mantissa so that the value
+    /// stays the same. Does nothing if the current scale is already greater
+    /// or equal.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `scale` is greater than 28 or the mantissa overflows.
     pub fn normalize(&mut self, scale: u32) {
-        let mut bstr = BitStr::from(&self.bits[0..3]);
+        if let Err(err) = check_scale(scale as i32) {
+            panic!("{err}");
+        }
 
-        bstr.pow(scale);
-        bstr.scale = scale as i32;
-        let normalized: S21Decimal = bstr.into();
+        let diff = scale as i32 - self.scale();
 
-        self.set_sign(normalized.sign());
-        self.set_scale(normalized.scale());
-        self.bits = normalized.bits;
-    }
+        if diff > 0 {
+            let mantissa =
+                mul_pow10(self.mantissa(), diff as u32).unwrap_or_else(|| panic!("overflow"));
 
-    pub fn is_max(&self) -> bool {
-        match (self.sign(), self.bits) {
-            (Sign::Positive, [-1, -1, -1, _]) => true,
-            _ => false,
+            *self = Self::from_mantissa(mantissa, self.sign(), scale as i32);
         }
     }
 
-    pub fn is_min(&self) -> bool {
-        match (self.sign(), self.bits) {
-            (Sign::Negative, [-1, -1, -1, _]) => true,
-            _ => false,
+    /// Returns the unsigned 96-bit coefficient, ignoring sign and scale.
+    pub const fn mantissa(&self) -> u128 {
+        (self.bits[0] as u32 as u128)
+            | (self.bits[1] as u32 as u128) << 32
+            | (self.bits[2] as u32 as u128) << 64
+    }
+
+    /// Returns bit `index` of the mantissa, counting from the least
+    /// significant.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not below 96.
+    pub const fn get_bit(&self, index: usize) -> bool {
+        let (word, bit) = Self::bit_position(index);
+        get_bit(self.bits[word], bit) == 1
+    }
+
+    /// Sets bit `index` of the mantissa, counting from the least significant.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not below 96.
+    pub const fn set_bit(&mut self, index: usize) {
+        let (word, bit) = Self::bit_position(index);
+        set_bit(&mut self.bits[word], bit);
+    }
+
+    /// Clears bit `index` of the mantissa, counting from the least
+    /// significant.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not below 96.
+    pub const fn clear_bit(&mut self, index: usize) {
+        let (word, bit) = Self::bit_position(index);
+        unset_bit(&mut self.bits[word], bit);
+    }
+
+    /// Iterates over the 96 mantissa bits, least significant first. Use
+    /// `.rev()` to go from the most significant.
+    pub fn iter_bits(&self) -> impl DoubleEndedIterator<Item = bool> + ExactSizeIterator + '_ {
+        (0..96).map(|index| self.get_bit(index))
+    }
+
+    const fn bit_position(index: usize) -> (usize, i32) {
+        assert!(index < 96, "bit index is out of range of 0..96");
+        (index / 32, (index % 32) as i32)
+    }
+
+    pub const fn is_max(&self) -> bool {
+        matches!((self.sign(), self.bits), (Sign::Positive, [-1, -1, -1, _]))
+    }
+
+    pub const fn is_min(&self) -> bool {
+        matches!((self.sign(), self.bits), (Sign::Negative, [-1, -1, -1, _]))
+    }
+
+    /// Flips the sign. Also available as the `-` operator.
+    pub const fn negate(self) -> Self {
+        Self {
+            bits: [
+                self.bits[0],
+                self.bits[1],
+                self.bits[2],
+                self.bits[3] ^ i32::MIN,
+            ],
         }
     }
-}
 
-impl Default for S21Decimal {
-    fn default() -> Self {
+    pub const fn abs(self) -> Self {
         Self {
-            bits: Default::default(),
+            bits: [
+                self.bits[0],
+                self.bits[1],
+                self.bits[2],
+                self.bits[3] & !i32::MIN,
+            ],
+        }
+    }
+
+    /// Compares numerically, so `1.0` and `1.00` as well as `0` and `-0` are
+    /// equal. `PartialEq` on the other hand compares the representation.
+    pub const fn cmp_value(&self, other: &Self) -> core::cmp::Ordering {
+        use core::cmp::Ordering::*;
+
+        if self.is_zero() && other.is_zero() {
+            return Equal;
+        }
+
+        match (self.is_negative(), other.is_negative()) {
+            (false, true) => Greater,
+            (true, false) => Less,
+            (false, false) => Self::cmp_magnitude(self, other),
+            (true, true) => Self::cmp_magnitude(other, self),
+        }
+    }
+
+    const fn cmp_magnitude(&self, other: &Self) -> core::cmp::Ordering {
+        use core::cmp::Ordering::*;
+
+        let (lscale, rscale) = (self.scale() as usize, other.scale() as usize);
+        let (left, right) = (self.mantissa(), other.mantissa());
+
+        let (lint, rint) = (left / POW10[lscale], right / POW10[rscale]);
+        let (left, right) = if lint == rint {
+            let scale = if lscale > rscale { lscale } else { rscale };
+
+            (
+                left % POW10[lscale] * POW10[scale - lscale],
+                right % POW10[rscale] * POW10[scale - rscale],
+            )
+        } else {
+            (lint, rint)
+        };
+
+        if left < right {
+            Less
+        } else if left > right {
+            Greater
+        } else {
+            Equal
         }
     }
 }
 
+const fn check_scale(scale: i32) -> Result<(), DecimalError> {
+    if scale < 0 || scale > MAX_SCALE {
+        Err(DecimalError::ScaleOutOfRange(scale))
+    } else {
+        Ok(())
+    }
+}
+
 impl From<i32> for S21Decimal {
     fn from(src: i32) -> Self {
-        let mut src = src;
-        let mut decimal = S21Decimal::default();
-
-        if src.is_negative() {
-            decimal.set_negative();
-            src = -src;
-        }
+        Self::from_integer(src, 0)
+    }
+}
 
-        decimal.bits[0] = src;
+/// Indexes the mantissa bits, least significant first.
+impl core::ops::Index<usize> for S21Decimal {
+    type Output = bool;
 
-        decimal
+    fn index(&self, index: usize) -> &bool {
+        if self.get_bit(index) {
+            &true
+        } else {
+            &false
+        }
     }
 }
 
-impl From<BitStr> for S21Decimal {
-    fn from(bstr: BitStr) -> Self {
-        let mut decimal = S21Decimal::default();
-        let mut index = BitStr::LENGTH;
+#[cfg(feature = "alloc")]
+/// A `BitStr` with a scale outside of 0..=28 is rescaled first, rounding
+/// half to even when the scale has to be reduced. Fails if a negative scale
+/// pushes the value past 96 bits.
+impl TryFrom<BitStr> for S21Decimal {
+    type Error = OverflowError;
 
-        for i in 0..3 {
-            for j in 0..32 {
-                index -= 1;
-                let bit = bstr.bytes[index] - 48;
+    fn try_from(bstr: BitStr) -> Result<Self, Self::Error> {
+        let bstr = if (0..=MAX_SCALE).contains(&bstr.scale) {
+            bstr
+        } else {
+            BitStr::try_from(DecStr::from(bstr))?
+        };
+        let mut decimal = S21Decimal::default();
 
-                if bit == 1 {
-                    set_bit(&mut decimal.bits[i], j);
-                }
+        for (index, bit) in bstr.iter_bits().enumerate() {
+            if bit {
+                decimal.set_bit(index);
             }
         }
 
         decimal.set_sign(bstr.sign);
         decimal.set_scale(bstr.scale);
-        decimal
+
+        Ok(decimal)
     }
 }
 
@@ -161,16 +402,18 @@ mod tests {
     fn decimal_from_int_neg() {
         let decimal = S21Decimal::from(-1);
         assert_eq!(1, decimal.bits[0]);
-        assert_eq!(true, decimal.is_negative());
+        assert!(decimal.is_negative());
     }
 
+    #[cfg(feature = "alloc")]
     #[test]
     fn decimal_from_bitstr() {
         let bstr = BitStr::default();
 
-        assert_eq!(S21Decimal::default(), bstr.into());
+        assert_eq!(Ok(S21Decimal::default()), bstr.try_into());
     }
 
+    #[cfg(feature = "alloc")]
     #[test]
     fn decimal_into_bstr() {
         let decimal = S21Decimal::default();
@@ -178,10 +421,288 @@ mod tests {
         assert_eq!(BitStr::default(), decimal.into());
     }
 
+    static LIMITS: [S21Decimal; 3] = [
+        S21Decimal::ZERO,
+        match S21Decimal::try_new(-5, 2) {
+            Ok(decimal) => decimal,
+            Err(_) => panic!(),
+        },
+        S21Decimal::MAX.negate(),
+    ];
+
+    #[test]
+    fn decimal_consts() {
+        assert_eq!(S21Decimal::default(), S21Decimal::ZERO);
+        assert_eq!(S21Decimal::from(1), S21Decimal::ONE);
+        assert_eq!(S21Decimal::from(-1), S21Decimal::NEGATIVE_ONE);
+        assert_eq!(S21Decimal::new(1, 28), S21Decimal::EPSILON);
+        assert!(S21Decimal::MAX.is_max());
+        assert!(S21Decimal::MIN.is_min());
+        assert_eq!(S21Decimal::MIN, LIMITS[2]);
+        assert_eq!(S21Decimal::new(-5, 2), LIMITS[1]);
+    }
+
+    #[test]
+    fn decimal_from_int_min() {
+        assert_eq!(
+            S21Decimal::from_parts(i32::MIN, 0, 0, true, 0).unwrap(),
+            S21Decimal::from(i32::MIN)
+        );
+    }
+
+    #[test]
+    fn decimal_negate_and_abs() {
+        assert_eq!(S21Decimal::new(45, 1), S21Decimal::new(-45, 1).negate());
+        assert_eq!(S21Decimal::new(-45, 1), S21Decimal::new(45, 1).negate());
+        assert_eq!(S21Decimal::new(45, 1), S21Decimal::new(-45, 1).abs());
+        assert_eq!(S21Decimal::new(-45, 1), -S21Decimal::new(45, 1));
+    }
+
+    #[test]
+    fn decimal_cmp_value() {
+        use core::cmp::Ordering::*;
+
+        let cases = [
+            ((10, 1), (100, 2), Equal),
+            ((0, 0), (0, 5), Equal),
+            ((15, 1), (149, 2), Greater),
+            ((-15, 1), (-149, 2), Less),
+            ((-1, 28), (0, 0), Less),
+            ((1, 0), (-2, 0), Greater),
+            ((5, 0), (49, 1), Greater),
+        ];
+
+        cases.iter().for_each(|((l, ls), (r, rs), expect)| {
+            let (l, r) = (S21Decimal::new(*l, *ls), S21Decimal::new(*r, *rs));
+            assert_eq!(*expect, l.cmp_value(&r));
+            assert_eq!(expect.reverse(), r.cmp_value(&l));
+        });
+
+        assert_eq!(
+            Equal,
+            S21Decimal::ZERO.cmp_value(&S21Decimal::ZERO.negate())
+        );
+        assert_eq!(Greater, S21Decimal::MAX.cmp_value(&S21Decimal::MIN));
+        assert_eq!(Less, S21Decimal::EPSILON.cmp_value(&S21Decimal::ONE));
+    }
+
+    #[test]
+    fn decimal_is_copy() {
+        let decimal = S21Decimal::new(-45, 1);
+        let sum = decimal + decimal;
+
+        assert_eq!(S21Decimal::new(-90, 1), sum);
+        assert_eq!(S21Decimal::new(-45, 1), decimal);
+    }
+
+    #[test]
+    fn decimal_layout_matches_c_struct() {
+        let decimal = S21Decimal::from_parts(1, 2, 3, true, 5).unwrap();
+        let words: [i32; 4] = unsafe { core::mem::transmute(decimal) };
+
+        assert_eq!([1, 2, 3, i32::MIN | 5 << 16], words);
+    }
+
+    #[test]
+    fn decimal_try_new() {
+        assert_eq!(Ok(S21Decimal::new(-45, 28)), S21Decimal::try_new(-45, 28));
+        assert_eq!(
+            Err(DecimalError::ScaleOutOfRange(29)),
+            S21Decimal::try_new(1, 29)
+        );
+        assert_eq!(
+            Err(DecimalError::ScaleOutOfRange(-1)),
+            S21Decimal::try_new(1, -1)
+        );
+    }
+
+    #[test]
+    #[should_panic = "scale 300 is out of range of 0..=28"]
+    fn decimal_new_scale_out_of_range() {
+        S21Decimal::new(1, 300);
+    }
+
+    #[test]
+    fn decimal_normalize_keeps_sign_and_value() {
+        let mut decimal = S21Decimal::new(-45, 1);
+        decimal.normalize(3);
+
+        assert_eq!(S21Decimal::new(-4500, 3), decimal);
+    }
+
+    #[test]
+    #[should_panic = "scale 29 is out of range of 0..=28"]
+    fn decimal_normalize_scale_out_of_range() {
+        S21Decimal::new(1, 0).normalize(29);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn decimal_from_bitstr_scale_above_28_rounds() {
+        let mut bstr = BitStr::from("11001");
+        bstr.scale = 30;
+
+        assert_eq!(Ok(S21Decimal::new(0, 28)), bstr.try_into());
+
+        let mut bstr = BitStr::from("11111010");
+        bstr.scale = 30;
+
+        assert_eq!(Ok(S21Decimal::new(2, 28)), bstr.try_into());
+
+        let mut bstr = BitStr::from("11111011");
+        bstr.scale = 30;
+
+        assert_eq!(Ok(S21Decimal::new(3, 28)), bstr.try_into());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn decimal_from_bitstr_negative_scale() {
+        let mut bstr = BitStr::from("101101");
+        bstr.sign = Sign::Negative;
+        bstr.scale = -2;
+
+        assert_eq!(Ok(S21Decimal::from(-4500)), bstr.try_into());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn decimal_from_bitstr_overflow() {
+        let mut bstr = BitStr::from("1");
+        bstr.scale = -40;
+
+        assert_eq!(Err(OverflowError), S21Decimal::try_from(bstr));
+
+        let mut bstr = BitStr::from("1".repeat(96).as_str());
+        bstr.scale = -1;
+
+        assert_eq!(Err(OverflowError), S21Decimal::try_from(bstr));
+    }
+
+    #[test]
+    fn decimal_from_parts() {
+        let decimal = S21Decimal::from_parts(1, 2, 3, true, 28).unwrap();
+
+        assert_eq!([1, 2, 3, i32::MIN | 28 << 16], decimal.to_raw_bits());
+        assert_eq!(1 | 2 << 32 | 3 << 64, decimal.mantissa());
+    }
+
+    #[test]
+    fn decimal_from_parts_scale_out_of_range() {
+        assert_eq!(
+            Err(DecimalError::ScaleOutOfRange(29)),
+            S21Decimal::from_parts(1, 0, 0, false, 29)
+        );
+    }
+
+    #[test]
+    fn decimal_from_raw_bits_round_trip() {
+        let bits = [-1, -1, -1, i32::MIN | 5 << 16];
+
+        assert_eq!(bits, S21Decimal::from_raw_bits(bits).unwrap().to_raw_bits());
+    }
+
+    #[test]
+    fn decimal_from_raw_bits_reserved_low_bits() {
+        assert_eq!(
+            Err(DecimalError::ReservedBitsSet(1)),
+            S21Decimal::from_raw_bits([0, 0, 0, 1])
+        );
+    }
+
+    #[test]
+    fn decimal_from_raw_bits_reserved_high_bits() {
+        assert_eq!(
+            Err(DecimalError::ReservedBitsSet(1 << 24)),
+            S21Decimal::from_raw_bits([0, 0, 0, 1 << 24])
+        );
+    }
+
+    #[test]
+    fn decimal_from_raw_bits_scale_out_of_range() {
+        assert_eq!(
+            Err(DecimalError::ScaleOutOfRange(255)),
+            S21Decimal::from_raw_bits([0, 0, 0, 255 << 16])
+        );
+    }
+
+    #[test]
+    fn decimal_mantissa_max() {
+        let decimal = S21Decimal::from_raw_bits([-1, -1, -1, 0]).unwrap();
+
+        assert_eq!(u128::MAX >> 32, decimal.mantissa());
+    }
+
     #[test]
     fn decimal_from_str_radix_base_10_num_45() {
-        let decimal = S21Decimal::from_str_radix("45", 10);
+        let decimal = S21Decimal::from_str_radix("45", 10).unwrap();
+
+        assert_eq!(S21Decimal::from(45), decimal);
+    }
+
+    #[test]
+    fn decimal_from_str_radix_base_16_neg() {
+        let decimal = S21Decimal::from_str_radix("-2D", 16).unwrap();
+
+        assert_eq!(S21Decimal::from(-45), decimal);
+    }
+
+    #[test]
+    fn decimal_from_str_prefixed_hex() {
+        let decimal = S21Decimal::from_str_prefixed("0x2d").unwrap();
 
         assert_eq!(S21Decimal::from(45), decimal);
     }
+
+    #[test]
+    fn decimal_from_str_prefixed_bare_prefix() {
+        for str in ["0x", "0o", "0B", "-0x", "+0b"] {
+            assert_eq!(
+                Err(ParseDecimalError::InvalidDigit),
+                S21Decimal::from_str_prefixed(str),
+                "{str}"
+            );
+        }
+    }
+
+    #[test]
+    fn decimal_get_set_clear_bit() {
+        let mut decimal = S21Decimal::new(-5, 3);
+
+        assert!(decimal.get_bit(0));
+        assert!(!decimal.get_bit(1));
+        assert!(decimal[2]);
+        assert!(!decimal[95]);
+
+        decimal.set_bit(95);
+        decimal.set_bit(32);
+        decimal.clear_bit(0);
+        assert_eq!(1 << 95 | 1 << 32 | 4, decimal.mantissa());
+        assert!(decimal.is_negative());
+        assert_eq!(3, decimal.scale());
+    }
+
+    #[test]
+    #[should_panic = "bit index is out of range of 0..96"]
+    fn decimal_set_bit_out_of_range() {
+        S21Decimal::default().set_bit(96);
+    }
+
+    #[test]
+    fn decimal_iter_bits() {
+        let five = S21Decimal::from(5);
+
+        assert_eq!(96, S21Decimal::MAX.iter_bits().filter(|&bit| bit).count());
+        assert!(five[0] && !five[1] && five[2]);
+        assert_eq!(Some(93), five.iter_bits().rev().position(|bit| bit));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn decimal_bits_match_bitstr() {
+        let decimal = S21Decimal::new(-123_456_789, 4);
+        let bstr = BitStr::from(decimal);
+
+        assert!(decimal.iter_bits().eq(bstr.iter_bits()));
+    }
 }
@@ -0,0 +1,57 @@
+/// Builds an [`S21Decimal`](crate::S21Decimal) from a numeric literal at
+/// compile time. Scientific notation is accepted as well.
+///
+/// ```
+/// use s21_decimal::{dec, S21Decimal};
+///
+/// const FEE: S21Decimal = dec!(0.005);
+///
+/// assert_eq!(S21Decimal::new(5, 3), FEE);
+/// assert_eq!(S21Decimal::new(-125, 9), dec!(-1.25e-7));
+/// ```
+///
+/// Literals that overflow 96 bits or need a scale above 28 do not compile:
+///
+/// ```compile_fail
+/// let max_plus_one = s21_decimal::dec!(79_228_162_514_264_337_593_543_950_336);
+/// ```
+///
+/// ```compile_fail
+/// let too_precise = s21_decimal::dec!(0.000_000_000_000_000_000_000_000_000_01);
+/// ```
+#[macro_export]
+macro_rules! dec {
+    ($lit:literal) => {{
+        const DECIMAL: $crate::S21Decimal = match $crate::S21Decimal::parse(stringify!($lit)) {
+            Ok(decimal) => decimal,
+            Err(err) => panic!("{}", err.as_str()),
+        };
+
+        DECIMAL
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::S21Decimal;
+
+    #[test]
+    fn dec_literals() {
+        assert_eq!(S21Decimal::ZERO, dec!(0));
+        assert_eq!(S21Decimal::new(45, 1), dec!(4.5));
+        assert_eq!(S21Decimal::new(-5, 3), dec!(-0.005));
+        assert_eq!(S21Decimal::from(300_000), dec!(3e5));
+        assert_eq!(S21Decimal::EPSILON, dec!(1e-28));
+        assert_eq!(
+            S21Decimal::MAX,
+            dec!(79_228_162_514_264_337_593_543_950_335)
+        );
+    }
+
+    #[test]
+    fn dec_in_static_table() {
+        static RATES: [S21Decimal; 2] = [dec!(0.01), dec!(-2.5)];
+
+        assert_eq!([S21Decimal::new(1, 2), S21Decimal::new(-25, 1)], RATES);
+    }
+}
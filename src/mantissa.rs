@@ -0,0 +1,140 @@
+//! Helpers for the 96-bit coefficient of [`S21Decimal`](crate::S21Decimal),
+//! held in the low bits of a `u128`.
+
+pub(crate) const MAX_MANTISSA: u128 = (1 << 96) - 1;
+
+pub(crate) const POW10: [u128; 29] = {
+    let mut table = [1; 29];
+    let mut i = 1;
+
+    while i < table.len() {
+        table[i] = table[i - 1] * 10;
+        i += 1;
+    }
+
+    table
+};
+
+/// Multiplies by `10^exp`, returning `None` if the result does not fit in 96
+/// bits.
+pub(crate) const fn mul_pow10(mantissa: u128, exp: u32) -> Option<u128> {
+    if exp as usize >= POW10.len() {
+        return if mantissa == 0 { Some(0) } else { None };
+    }
+
+    match mantissa.checked_mul(POW10[exp as usize]) {
+        Some(result) if result <= MAX_MANTISSA => Some(result),
+        _ => None,
+    }
+}
+
+/// Divides by `10^exp`, rounding half to even.
+pub(crate) const fn div_pow10(mantissa: u128, exp: u32) -> u128 {
+    if exp == 0 {
+        return mantissa;
+    }
+
+    if exp as usize >= POW10.len() {
+        return 0;
+    }
+
+    let divisor = POW10[exp as usize];
+    let quotient = mantissa / divisor;
+    let remainder = mantissa % divisor;
+    let half = divisor / 2;
+
+    if remainder > half || (remainder == half && quotient % 2 == 1) {
+        quotient + 1
+    } else {
+        quotient
+    }
+}
+
+pub(crate) const fn count_digits(mantissa: u128) -> u32 {
+    if mantissa == 0 {
+        1
+    } else {
+        mantissa.ilog10() + 1
+    }
+}
+
+/// ASCII digits of a mantissa in radix 2 to 36, written into a stack buffer.
+pub(crate) struct Digits {
+    buf: [u8; 128],
+    start: usize,
+}
+
+impl Digits {
+    pub(crate) fn new(mantissa: u128) -> Self {
+        Self::with_radix(mantissa, 10, false)
+    }
+
+    pub(crate) fn with_radix(mut mantissa: u128, radix: u32, upper: bool) -> Self {
+        let mut digits = Self {
+            buf: [b'0'; 128],
+            start: 128,
+        };
+
+        loop {
+            let digit = (mantissa % radix as u128) as u8;
+            digits.start -= 1;
+            digits.buf[digits.start] = match digit {
+                0..=9 => b'0' + digit,
+                _ if upper => b'A' + digit - 10,
+                _ => b'a' + digit - 10,
+            };
+            mantissa /= radix as u128;
+
+            if mantissa == 0 {
+                break digits;
+            }
+        }
+    }
+
+    pub(crate) fn as_str(&self) -> &str {
+        core::str::from_utf8(&self.buf[self.start..]).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mantissa_pow10_table() {
+        assert_eq!(1, POW10[0]);
+        assert_eq!(10_000_000_000_000_000_000_000_000_000, POW10[28]);
+    }
+
+    #[test]
+    fn mantissa_mul_pow10_overflow() {
+        assert_eq!(Some(POW10[28]), mul_pow10(1, 28));
+        assert_eq!(None, mul_pow10(8, 28));
+        assert_eq!(None, mul_pow10(MAX_MANTISSA, 28));
+        assert_eq!(None, mul_pow10(1, 29));
+        assert_eq!(Some(0), mul_pow10(0, 40));
+    }
+
+    #[test]
+    fn mantissa_div_pow10_half_even() {
+        assert_eq!(2, div_pow10(25, 1));
+        assert_eq!(4, div_pow10(35, 1));
+        assert_eq!(3, div_pow10(251, 2));
+        assert_eq!(0, div_pow10(MAX_MANTISSA, 30));
+    }
+
+    #[test]
+    fn mantissa_digits() {
+        assert_eq!("0", Digits::new(0).as_str());
+        assert_eq!(
+            "79228162514264337593543950335",
+            Digits::new(MAX_MANTISSA).as_str()
+        );
+        assert_eq!(39, Digits::new(u128::MAX).as_str().len());
+        assert_eq!(29, count_digits(MAX_MANTISSA));
+        assert_eq!(128, Digits::with_radix(u128::MAX, 2, false).as_str().len());
+        assert_eq!("ff", Digits::with_radix(255, 16, false).as_str());
+        assert_eq!("FF", Digits::with_radix(255, 16, true).as_str());
+        assert_eq!("z", Digits::with_radix(35, 36, false).as_str());
+    }
+}
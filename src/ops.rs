@@ -1,39 +1,102 @@
-use crate::{BitStr, S21Decimal, Sign};
+use crate::mantissa::{div_pow10, MAX_MANTISSA, POW10};
+use crate::{S21Decimal, Sign};
 
-use std::cmp::Ordering::{Greater, Less};
+use core::cmp::Ordering::{Equal, Greater, Less};
 
-impl std::ops::Add for S21Decimal {
+impl core::ops::Add for S21Decimal {
     type Output = Self;
 
     fn add(self, rhs: Self) -> Self::Output {
-        let mut left = self;
-        let mut right = rhs;
-
-        match (left.is_max(), left.sign(), right.is_max(), right.sign()) {
+        match (self.is_max(), self.sign(), rhs.is_max(), rhs.sign()) {
             (true, Sign::Positive, _, Sign::Positive)
             | (_, Sign::Positive, true, Sign::Positive) => panic!("overflow"),
             _ => (),
         }
 
-        match (left.is_min(), left.sign(), right.is_min(), right.sign()) {
+        match (self.is_min(), self.sign(), rhs.is_min(), rhs.sign()) {
             (true, Sign::Negative, _, Sign::Negative)
             | (_, Sign::Negative, true, Sign::Negative) => panic!("underflow"),
             _ => (),
         }
 
-        match left.scale().cmp(&right.scale()) {
-            Less => left.normalize(right.scale() as u32),
-            Greater => right.normalize(left.scale() as u32),
-            _ => (),
+        let (left, right, scale) = align(
+            (self.mantissa(), self.scale()),
+            (rhs.mantissa(), rhs.scale()),
+        );
+
+        let (sum, sign) = match (self.sign() == rhs.sign(), left.cmp(&right)) {
+            (true, _) => (left + right, self.sign()),
+            (false, Greater) => (left - right, self.sign()),
+            (false, Less) => (right - left, rhs.sign()),
+            (false, Equal) => (0, Sign::Positive),
+        };
+
+        let (sum, scale) = fit(sum, scale).unwrap_or_else(|| match sign {
+            Sign::Positive => panic!("overflow"),
+            Sign::Negative => panic!("underflow"),
+        });
+
+        S21Decimal::from_mantissa(sum, sign, scale)
+    }
+}
+
+impl core::ops::Neg for S21Decimal {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        self.negate()
+    }
+}
+
+/// Largest aligned operand: the sum of two of them, each with an appended
+/// sticky digit, still fits in a `u128`.
+const ALIGN_LIMIT: u128 = u128::MAX / 20;
+
+/// Brings two `(mantissa, scale)` pairs to a common scale, in a `u128` that is
+/// wider than the 96-bit result. The operand with the lower scale is scaled up
+/// as far as that allows. Digits of the other one that still fall below the
+/// common scale are truncated and recorded in an extra sticky digit, so that
+/// the sum is rounded only once, in `fit`.
+fn align(left: (u128, i32), right: (u128, i32)) -> (u128, u128, i32) {
+    let swapped = left.1 > right.1;
+    let ((low, low_scale), (high, high_scale)) = if swapped {
+        (right, left)
+    } else {
+        (left, right)
+    };
+
+    let mut scale = high_scale;
+    let low = loop {
+        match low.checked_mul(POW10[(scale - low_scale) as usize]) {
+            Some(low) if low <= ALIGN_LIMIT => break low,
+            _ => scale -= 1,
         }
+    };
+
+    let (low, high, scale) = if scale == high_scale {
+        (low, high, scale)
+    } else {
+        let divisor = POW10[(high_scale - scale) as usize];
+        let sticky = (high % divisor != 0) as u128;
 
-        let lbs = BitStr::new(left.sign(), left.scale(), &left.bits[0..3]);
-        let rbs = BitStr::new(right.sign(), right.scale(), &right.bits[0..3]);
+        (low * 10, high / divisor * 10 + sticky, scale + 1)
+    };
 
-        S21Decimal::from(lbs + rbs)
+    if swapped {
+        (high, low, scale)
+    } else {
+        (low, high, scale)
     }
 }
 
+/// Reduces the scale of a result, rounding half to even, until the mantissa
+/// fits in 96 bits. Returns `None` if it does not fit even at scale 0.
+fn fit(mantissa: u128, scale: i32) -> Option<(u128, i32)> {
+    (0..=scale as u32)
+        .map(|exp| (div_pow10(mantissa, exp), scale - exp as i32))
+        .find(|(mantissa, _)| *mantissa <= MAX_MANTISSA)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -43,10 +106,10 @@ mod tests {
             #[test]
             $(#[$attr])?
             fn $name() {
-                let l = S21Decimal::from_str_radix(stringify!($lhs), 10);
-                let r = S21Decimal::from_str_radix(stringify!($rhs), 10);
+                let l = S21Decimal::from_str_radix(stringify!($lhs), 10).unwrap();
+                let r = S21Decimal::from_str_radix(stringify!($rhs), 10).unwrap();
 
-                assert_eq!(S21Decimal::from_str_radix(stringify!($expect), 10), l + r);
+                assert_eq!(S21Decimal::from_str_radix(stringify!($expect), 10).unwrap(), l + r);
             }
         };
     }
@@ -84,6 +147,36 @@ mod tests {
         expect: 4.995
     });
 
+    decimal_add_tc!(decimal_add_left_neg_int_right_scale_3 {
+        left: -5,
+        right: 0.005,
+        expect: -4.995
+    });
+
+    decimal_add_tc!(decimal_add_left_scale_1_right_scale_3 {
+        left: 4.5,
+        right: 0.001,
+        expect: 4.501
+    });
+
+    decimal_add_tc!(decimal_add_align_rounds_half_even {
+        left: 10_000_000_000_000_000_000_000_000_000,
+        right: 0.5,
+        expect: 10_000_000_000_000_000_000_000_000_000
+    });
+
+    decimal_add_tc!(decimal_add_align_rounds_up {
+        left: 10_000_000_000_000_000_000_000_000_000,
+        right: 0.6,
+        expect: 10_000_000_000_000_000_000_000_000_001
+    });
+
+    decimal_add_tc!(decimal_add_sum_reduces_scale {
+        left: 5.000_000_000_000_000_000_000_000_000_0,
+        right: 5,
+        expect: 10.000_000_000_000_000_000_000_000_000
+    });
+
     decimal_add_tc!(decimal_add_left_right_scale_5 {
         left: 0.00005,
         right: 0.00005,
@@ -227,4 +320,22 @@ mod tests {
             expect: 0
         }
     );
+
+    decimal_add_tc!(decimal_add_rounds_once {
+        left: 10000000000000000000000000001,
+        right: -1.5,
+        expect: 10000000000000000000000000000
+    });
+
+    decimal_add_tc!(decimal_add_sticky_breaks_tie {
+        left: 10000000000000000000000000001,
+        right: -1.5000000000000000000000000001,
+        expect: 9999999999999999999999999999
+    });
+
+    decimal_add_tc!(decimal_add_sticky_keeps_tie_above {
+        left: 10000000000000000000000000000,
+        right: 0.5000000000000000000000000001,
+        expect: 10000000000000000000000000001
+    });
 }
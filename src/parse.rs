@@ -0,0 +1,480 @@
+use crate::mantissa::{mul_pow10, MAX_MANTISSA};
+use crate::{ParseDecimalError, S21Decimal, Sign};
+
+const MAX_SCALE: i64 = 28;
+
+impl core::str::FromStr for S21Decimal {
+    type Err = ParseDecimalError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl S21Decimal {
+    /// Parses plain decimals (`-4.5`, `0.000_05`) as well as scientific and
+    /// engineering notation (`1.25E-7`, `3e+5`). The exponent is folded into
+    /// the scale when negative and into the mantissa when positive.
+    ///
+    /// This is the `const` parser behind both `FromStr` and [`dec!`](crate::dec).
+    pub const fn parse(str: &str) -> Result<Self, ParseDecimalError> {
+        let bytes = str.as_bytes();
+
+        let (sign, start) = match bytes.first() {
+            Some(b'-') => (Sign::Negative, 1),
+            Some(b'+') => (Sign::Positive, 1),
+            Some(_) => (Sign::Positive, 0),
+            None => return Err(ParseDecimalError::Empty),
+        };
+
+        let mut end = start;
+        while end < bytes.len() && bytes[end] != b'e' && bytes[end] != b'E' {
+            end += 1;
+        }
+
+        let exponent = if end < bytes.len() {
+            match parse_exponent(bytes, end + 1) {
+                Ok(exponent) => exponent,
+                Err(err) => return Err(err),
+            }
+        } else {
+            0
+        };
+
+        match parse_mantissa(bytes, start, end) {
+            Ok((mantissa, scale)) => finish(sign, mantissa, scale - exponent),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Parses an integer in any radix from 2 to 36, see
+    /// [`BitStr::from_str_radix`](crate::BitStr::from_str_radix).
+    pub fn from_str_radix(str: &str, radix: u32) -> Result<Self, ParseDecimalError> {
+        let (sign, mantissa, scale) = parse_radix(str, radix)?;
+
+        finish(sign, mantissa, scale)
+    }
+
+    /// Parses an integer with an optional `0x`, `0o` or `0b` prefix, see
+    /// [`BitStr::from_str_prefixed`](crate::BitStr::from_str_prefixed).
+    pub fn from_str_prefixed(str: &str) -> Result<Self, ParseDecimalError> {
+        let (sign, mantissa, scale) = parse_prefixed(str)?;
+
+        finish(sign, mantissa, scale)
+    }
+}
+
+/// Builds a decimal from a parsed mantissa and a possibly out of range scale.
+/// Negative scales are multiplied into the mantissa, and trailing zeros are
+/// dropped while the scale is above 28.
+const fn finish(
+    sign: Sign,
+    mut mantissa: u128,
+    mut scale: i64,
+) -> Result<S21Decimal, ParseDecimalError> {
+    if mantissa == 0 && scale < 0 {
+        scale = 0;
+    } else if mantissa == 0 && scale > MAX_SCALE {
+        scale = MAX_SCALE;
+    }
+
+    if scale < 0 {
+        let exp = if -scale > u32::MAX as i64 {
+            u32::MAX
+        } else {
+            -scale as u32
+        };
+
+        mantissa = match mul_pow10(mantissa, exp) {
+            Some(mantissa) => mantissa,
+            None => return Err(ParseDecimalError::Overflow),
+        };
+        scale = 0;
+    }
+
+    while scale > MAX_SCALE && mantissa.is_multiple_of(10) {
+        mantissa /= 10;
+        scale -= 1;
+    }
+
+    if scale > MAX_SCALE {
+        return Err(ParseDecimalError::ScaleOverflow);
+    }
+
+    Ok(S21Decimal::from_mantissa(mantissa, sign, scale as i32))
+}
+
+pub(crate) fn split_sign(s: &str) -> Result<(Sign, &str), ParseDecimalError> {
+    match s.as_bytes().first() {
+        Some(b'-') => Ok((Sign::Negative, &s[1..])),
+        Some(b'+') => Ok((Sign::Positive, &s[1..])),
+        Some(_) => Ok((Sign::Positive, s)),
+        None => Err(ParseDecimalError::Empty),
+    }
+}
+
+/// Parses the digits of an integer in `radix`, or of a decimal with a single
+/// point when `radix` is 10, into a sign, a 96-bit mantissa and a scale.
+pub(crate) fn parse_radix(str: &str, radix: u32) -> Result<(Sign, u128, i64), ParseDecimalError> {
+    assert_radix(radix);
+
+    let (sign, digits) = split_sign(str)?;
+    let (mantissa, scale) = parse_unsigned(digits, radix)?;
+
+    Ok((sign, mantissa, scale))
+}
+
+/// Like [`parse_radix`], with the radix given by a `0x`, `0o` or `0b` prefix
+/// after the sign, or 10 when there is none.
+pub(crate) fn parse_prefixed(str: &str) -> Result<(Sign, u128, i64), ParseDecimalError> {
+    let (sign, radix, digits) = split_prefix(str)?;
+    let (mantissa, scale) = parse_unsigned(digits, radix)?;
+
+    Ok((sign, mantissa, scale))
+}
+
+/// Radix 10 shares [`parse_mantissa`] with `parse`, so trailing zeros that
+/// do not fit in 96 bits end up in the scale the same way.
+fn parse_unsigned(digits: &str, radix: u32) -> Result<(u128, i64), ParseDecimalError> {
+    if radix == 10 {
+        return parse_mantissa(digits.as_bytes(), 0, digits.len());
+    }
+
+    let mut mantissa = 0u128;
+    let scale = push_digits(digits, radix, |radix, digit| {
+        mantissa = mantissa * radix as u128 + digit as u128;
+
+        match mantissa > MAX_MANTISSA {
+            true => Err(ParseDecimalError::Overflow),
+            false => Ok(()),
+        }
+    })?;
+
+    Ok((mantissa, scale as i64))
+}
+
+/// Validates the digits of [`parse_radix`] and hands each one, most
+/// significant first, to `push` along with the radix. Returns the sign and
+/// the scale, leaving the accumulation of the mantissa to the caller.
+pub(crate) fn parse_digits(
+    str: &str,
+    radix: u32,
+    push: impl FnMut(u32, u32) -> Result<(), ParseDecimalError>,
+) -> Result<(Sign, i32), ParseDecimalError> {
+    assert_radix(radix);
+
+    let (sign, digits) = split_sign(str)?;
+
+    Ok((sign, push_digits(digits, radix, push)?))
+}
+
+/// Like [`parse_digits`], with the radix taken from the prefix as in
+/// [`parse_prefixed`].
+pub(crate) fn parse_prefixed_digits(
+    str: &str,
+    push: impl FnMut(u32, u32) -> Result<(), ParseDecimalError>,
+) -> Result<(Sign, i32), ParseDecimalError> {
+    let (sign, radix, digits) = split_prefix(str)?;
+
+    Ok((sign, push_digits(digits, radix, push)?))
+}
+
+fn assert_radix(radix: u32) {
+    assert!(
+        (2..=36).contains(&radix),
+        "radix must be in range of 2..=36, got {radix}"
+    );
+}
+
+/// Splits off the sign and a `0x`, `0o` or `0b` prefix, returning the radix
+/// it stands for, or 10 when there is none.
+fn split_prefix(str: &str) -> Result<(Sign, u32, &str), ParseDecimalError> {
+    let (sign, unsigned) = split_sign(str)?;
+
+    let (radix, digits) = match unsigned.get(..2) {
+        Some("0x" | "0X") => (16, &unsigned[2..]),
+        Some("0o" | "0O") => (8, &unsigned[2..]),
+        Some("0b" | "0B") => (2, &unsigned[2..]),
+        _ => return Ok((sign, 10, unsigned)),
+    };
+
+    if digits.is_empty() || digits.starts_with(['-', '+']) {
+        return Err(ParseDecimalError::InvalidDigit);
+    }
+
+    Ok((sign, radix, digits))
+}
+
+/// Feeds unsigned digits to `push` and returns the scale, which only a point
+/// in radix 10 can set.
+fn push_digits(
+    str: &str,
+    radix: u32,
+    mut push: impl FnMut(u32, u32) -> Result<(), ParseDecimalError>,
+) -> Result<i32, ParseDecimalError> {
+    let mut digits = 0;
+    let mut scale = None;
+
+    for ch in str.chars() {
+        match (ch, &mut scale) {
+            ('_', _) => continue,
+            ('.', None) if radix == 10 => {
+                scale = Some(0);
+                continue;
+            }
+            (_, Some(scale)) => *scale += 1,
+            _ => (),
+        }
+
+        let digit = ch.to_digit(radix).ok_or(ParseDecimalError::InvalidDigit)?;
+        push(radix, digit)?;
+        digits += 1;
+    }
+
+    if digits == 0 {
+        return Err(ParseDecimalError::InvalidDigit);
+    }
+
+    Ok(scale.unwrap_or(0))
+}
+
+/// Parses decimal digits with an optional point in `bytes[start..end]`.
+/// Trailing zeros that do not fit in 96 bits are dropped and accounted for in
+/// the returned scale, which may therefore be negative.
+const fn parse_mantissa(
+    bytes: &[u8],
+    start: usize,
+    end: usize,
+) -> Result<(u128, i64), ParseDecimalError> {
+    let mut mantissa = 0u128;
+    let mut scale = 0;
+    let mut digits = 0;
+    let mut point = false;
+    let mut i = start;
+
+    while i < end {
+        let byte = bytes[i];
+        i += 1;
+
+        match byte {
+            b'_' => continue,
+            b'.' if !point => {
+                point = true;
+                continue;
+            }
+            b'0'..=b'9' => (),
+            _ => return Err(ParseDecimalError::InvalidDigit),
+        }
+
+        let digit = (byte - b'0') as u128;
+        let next = mantissa * 10 + digit;
+        digits += 1;
+
+        if point {
+            scale += 1;
+        }
+
+        if next <= MAX_MANTISSA {
+            mantissa = next;
+        } else if digit == 0 {
+            scale -= 1;
+        } else {
+            return Err(ParseDecimalError::Overflow);
+        }
+    }
+
+    if digits == 0 {
+        return Err(ParseDecimalError::InvalidDigit);
+    }
+
+    Ok((mantissa, scale))
+}
+
+/// Parses a signed exponent in `bytes[start..]` that fits in an `i32`.
+const fn parse_exponent(bytes: &[u8], start: usize) -> Result<i64, ParseDecimalError> {
+    let (negative, mut i) = match start < bytes.len() {
+        true if bytes[start] == b'-' => (true, start + 1),
+        true if bytes[start] == b'+' => (false, start + 1),
+        _ => (false, start),
+    };
+
+    if i == bytes.len() {
+        return Err(ParseDecimalError::InvalidExponent);
+    }
+
+    let mut exponent = 0i64;
+
+    while i < bytes.len() {
+        if !bytes[i].is_ascii_digit() {
+            return Err(ParseDecimalError::InvalidExponent);
+        }
+
+        exponent = exponent * 10 + (bytes[i] - b'0') as i64;
+        i += 1;
+
+        if exponent > i32::MAX as i64 {
+            return Err(ParseDecimalError::InvalidExponent);
+        }
+    }
+
+    Ok(if negative { -exponent } else { exponent })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    macro_rules! decimal_parse_tc {
+        ($name:ident { from: $from:literal, expect: $expect:expr }) => {
+            #[test]
+            fn $name() {
+                assert_eq!($expect, $from.parse::<S21Decimal>());
+            }
+        };
+    }
+
+    decimal_parse_tc!(decimal_parse_plain {
+        from: "-4.5",
+        expect: Ok(S21Decimal::new(-45, 1))
+    });
+
+    decimal_parse_tc!(decimal_parse_underscores {
+        from: "123_456.000_1",
+        expect: S21Decimal::from_str_radix("123456.0001", 10)
+    });
+
+    decimal_parse_tc!(decimal_parse_exp_negative {
+        from: "1.25E-7",
+        expect: Ok(S21Decimal::new(125, 9))
+    });
+
+    decimal_parse_tc!(decimal_parse_exp_positive {
+        from: "3e+5",
+        expect: Ok(S21Decimal::from(300_000))
+    });
+
+    decimal_parse_tc!(decimal_parse_exp_positive_consumes_scale {
+        from: "-2.5e1",
+        expect: Ok(S21Decimal::from(-25))
+    });
+
+    decimal_parse_tc!(decimal_parse_exp_positive_max {
+        from: "7.9228162514264337593543950335e28",
+        expect: S21Decimal::from_str_radix("79228162514264337593543950335", 10)
+    });
+
+    decimal_parse_tc!(decimal_parse_exp_trailing_zeroes_fit_scale {
+        from: "1.000e-28",
+        expect: Ok(S21Decimal::new(1, 28))
+    });
+
+    decimal_parse_tc!(decimal_parse_exp_overflow {
+        from: "8e28",
+        expect: Err(ParseDecimalError::Overflow)
+    });
+
+    decimal_parse_tc!(decimal_parse_overflow {
+        from: "79_228_162_514_264_337_593_543_950_336",
+        expect: Err(ParseDecimalError::Overflow)
+    });
+
+    decimal_parse_tc!(decimal_parse_exp_scale_overflow {
+        from: "1.5e-28",
+        expect: Err(ParseDecimalError::ScaleOverflow)
+    });
+
+    decimal_parse_tc!(decimal_parse_exp_far_overflow {
+        from: "1e2000000000",
+        expect: Err(ParseDecimalError::Overflow)
+    });
+
+    decimal_parse_tc!(decimal_parse_exp_zero_clamps_scale {
+        from: "0.0e-40",
+        expect: Ok(S21Decimal::new(0, 28))
+    });
+
+    decimal_parse_tc!(decimal_parse_long_trailing_zeroes {
+        from: "1.000_000_000_000_000_000_000_000_000_000_000",
+        expect: S21Decimal::from_str_radix("1.000_000_000_000_000_000_000_000_000_0", 10)
+    });
+
+    decimal_parse_tc!(decimal_parse_long_integer_trailing_zeroes {
+        from: "10_000_000_000_000_000_000_000_000_000_000e-10",
+        expect: S21Decimal::from_str_radix("1_000_000_000_000_000_000_000.000_000_0", 10)
+    });
+
+    decimal_parse_tc!(decimal_parse_too_many_significant_digits {
+        from: "0.123_456_789_012_345_678_901_234_567_890_1",
+        expect: Err(ParseDecimalError::Overflow)
+    });
+
+    decimal_parse_tc!(decimal_parse_exp_missing {
+        from: "1e",
+        expect: Err(ParseDecimalError::InvalidExponent)
+    });
+
+    decimal_parse_tc!(decimal_parse_exp_huge {
+        from: "1e99999999999",
+        expect: Err(ParseDecimalError::InvalidExponent)
+    });
+
+    decimal_parse_tc!(decimal_parse_invalid_digit {
+        from: "4.5f",
+        expect: Err(ParseDecimalError::InvalidDigit)
+    });
+
+    decimal_parse_tc!(decimal_parse_exp_invalid_digit {
+        from: "1e5.0",
+        expect: Err(ParseDecimalError::InvalidExponent)
+    });
+
+    decimal_parse_tc!(decimal_parse_only_sign {
+        from: "-",
+        expect: Err(ParseDecimalError::InvalidDigit)
+    });
+
+    decimal_parse_tc!(decimal_parse_empty {
+        from: "",
+        expect: Err(ParseDecimalError::Empty)
+    });
+
+    #[test]
+    fn decimal_parse_matches_from_str_radix() {
+        let cases = [
+            "1.0000000000000000000000000000000",
+            "-10_000_000_000_000_000_000_000_000_000_000",
+            "0.000_000_000_000_000_000_000_000_000_000_0",
+            "79228162514264337593543950335",
+            "79228162514264337593543950336",
+            "0.123_456_789_012_345_678_901_234_567_890_1",
+            "-4.5",
+            "1.",
+            ".5",
+            "-",
+            "",
+        ];
+
+        for case in cases {
+            assert_eq!(
+                case.parse::<S21Decimal>(),
+                S21Decimal::from_str_radix(case, 10),
+                "{case}"
+            );
+            assert_eq!(
+                case.parse::<S21Decimal>(),
+                S21Decimal::from_str_prefixed(case),
+                "{case}"
+            );
+        }
+    }
+
+    #[test]
+    fn decimal_parse_const() {
+        const PRICE: S21Decimal = match S21Decimal::parse("-0.005") {
+            Ok(decimal) => decimal,
+            Err(_) => panic!(),
+        };
+
+        assert_eq!(S21Decimal::new(-5, 3), PRICE);
+    }
+}